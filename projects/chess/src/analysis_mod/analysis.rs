@@ -0,0 +1,89 @@
+use crate::board_mod::board::Board;
+use crate::errors::position_error::PositionError;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::results::fight_result::FightResult;
+use std::fmt;
+
+/// Represents what the analyser finds out about a Board.
+#[derive(Debug)]
+pub struct Analysis {
+    pub fen: String,
+    /// Result of the fight, only for tables with two pieces of different colors.
+    pub fight: Option<FightResult<'static>>,
+    /// Whether the position could happen in a game, or why not.
+    pub validation: Result<(), PositionError>,
+    /// Moves of the side to move. They are only counted in valid positions.
+    pub legal_moves: Option<usize>,
+}
+
+impl Analysis {
+    /// Analyses the Board: its FEN, the fight between its pieces and, in valid positions, the moves.
+    pub fn new(board: &Board) -> Analysis {
+        let fight = board
+            .fight()
+            .ok()
+            .and_then(|result| FightResult::from_output(result.output));
+        let validation = board.validate();
+        let legal_moves = validation
+            .is_ok()
+            .then(|| Mailbox::from_board(board).legal_moves().len());
+
+        Analysis {
+            fen: board.to_fen(),
+            fight,
+            validation,
+            legal_moves,
+        }
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.fen)?;
+        if let Some(fight) = &self.fight {
+            write!(f, " | fight {}", fight.output)?;
+        }
+        match (&self.validation, self.legal_moves) {
+            (Ok(()), Some(moves)) => write!(f, " | valid, {} legal moves", moves),
+            (Err(e), _) => write!(f, " | invalid: {}", e),
+            (Ok(()), None) => write!(f, " | valid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    #[test]
+    fn test_analyse_table() {
+        let board = Board::from_table(include_str!("../../test/table1.txt")).unwrap();
+        let analysis = Analysis::new(&board);
+
+        assert_eq!(
+            analysis.fight.as_ref().map(|result| result.output),
+            Some(FightResult::BLACK_WINS.output)
+        );
+        assert_eq!(
+            analysis.validation,
+            Err(PositionError::MissingKing(Color::White))
+        );
+        assert_eq!(analysis.legal_moves, None);
+        assert_eq!(
+            analysis.to_string(),
+            "8/8/3q4/8/8/6R1/8/8 w - - 0 1 | fight N | invalid: White has no king"
+        );
+    }
+
+    #[test]
+    fn test_analyse_position() {
+        let board =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let analysis = Analysis::new(&board);
+
+        assert!(analysis.fight.is_none());
+        assert_eq!(analysis.legal_moves, Some(20));
+        assert!(analysis.to_string().ends_with(" | valid, 20 legal moves"));
+    }
+}
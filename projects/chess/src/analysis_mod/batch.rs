@@ -0,0 +1,288 @@
+use crate::analysis_mod::analysis::Analysis;
+use crate::board_mod::board::Board;
+use crate::errors::chess_error::ChessError;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Represents a Board waiting to be analysed, named after where it was read from.
+/// Boards that could not be read keep their error, so it is reported in its place.
+pub struct BoardInput {
+    pub name: String,
+    pub board: Result<Board, ChessError<'static>>,
+}
+
+/// Represents the analysis of one of the inputs, or the reason it could not be read.
+pub struct Report {
+    pub index: usize,
+    pub name: String,
+    pub analysis: Result<Analysis, ChessError<'static>>,
+}
+
+/// Represents how many inputs have been reported out of the total.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.done, self.total)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.analysis {
+            Ok(analysis) => write!(f, "{}: {}", self.name, analysis),
+            Err(e) => write!(f, "{}: ERROR: [{}]", self.name, e),
+        }
+    }
+}
+
+/// Reads one Board per FEN line. Empty lines and lines starting with '#' are skipped.
+/// Each Board is named after its line number.
+pub fn read_fen_lines(text: &str) -> Vec<BoardInput> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| BoardInput {
+            name: format!("line {}", number),
+            board: Board::from_fen(line),
+        })
+        .collect()
+}
+
+/// Reads one Board per file in the table format. Each Board is named after its file.
+pub fn read_table_files(paths: &[String]) -> Vec<BoardInput> {
+    paths
+        .iter()
+        .map(|path| BoardInput {
+            name: path.clone(),
+            board: fs::read_to_string(path)
+                .map_err(|_| ChessError::FILE_READING)
+                .and_then(|table| Board::from_table(&table)),
+        })
+        .collect()
+}
+
+/// Analyses the Boards on the given number of threads.
+/// Reports are handed to `on_report` as soon as every input before them is reported, so they come
+/// in the same order as the inputs, each one with the progress made so far.
+/// A Board whose analysis fails is reported with an error, and the rest are still analysed.
+pub fn analyse_all<F>(inputs: Vec<BoardInput>, threads: usize, on_report: F)
+where
+    F: FnMut(Report, Progress),
+{
+    analyse_all_with(inputs, threads, Analysis::new, on_report)
+}
+
+/// Analyses the Boards like `analyse_all`, using the given function to analyse each one.
+fn analyse_all_with<F>(
+    inputs: Vec<BoardInput>,
+    threads: usize,
+    analyse: fn(&Board) -> Analysis,
+    mut on_report: F,
+) where
+    F: FnMut(Report, Progress),
+{
+    let total = inputs.len();
+    let (job_sender, job_receiver) = mpsc::channel::<(usize, Board)>();
+    let (result_sender, result_receiver) = mpsc::channel();
+    let job_receiver = Arc::new(Mutex::new(job_receiver));
+
+    let workers: Vec<_> = (0..threads.clamp(1, total.max(1)))
+        .map(|_| {
+            let jobs = Arc::clone(&job_receiver);
+            let results = result_sender.clone();
+            thread::spawn(move || loop {
+                // The lock is released as soon as the job is taken, before analysing it.
+                let job = jobs.lock().map(|jobs| jobs.recv());
+                let (index, board) = match job {
+                    Ok(Ok(job)) => job,
+                    _ => return,
+                };
+                // A panic is caught so that the worker goes on with the next Board.
+                let analysis = panic::catch_unwind(AssertUnwindSafe(|| analyse(&board)))
+                    .map_err(|_| ChessError::ANALYSIS);
+                if results.send((index, analysis)).is_err() {
+                    return;
+                }
+            })
+        })
+        .collect();
+    drop(result_sender);
+
+    let mut names = Vec::with_capacity(total);
+    let mut pending = BTreeMap::new();
+    for (index, input) in inputs.into_iter().enumerate() {
+        names.push(input.name);
+        match input.board {
+            Ok(board) => {
+                let _ = job_sender.send((index, board));
+            }
+            Err(e) => {
+                pending.insert(index, Err(e));
+            }
+        }
+    }
+    drop(job_sender);
+
+    let mut names = names.into_iter();
+    for done in 1..=total {
+        let analysis = loop {
+            if let Some(analysis) = pending.remove(&(done - 1)) {
+                break analysis;
+            }
+            match result_receiver.recv() {
+                Ok((index, analysis)) => {
+                    pending.insert(index, analysis);
+                }
+                // Every worker is gone: the missing analyses will never come.
+                Err(_) => break Err(ChessError::ANALYSIS),
+            }
+        };
+        let report = Report {
+            index: done - 1,
+            name: names.next().unwrap_or_default(),
+            analysis,
+        };
+        on_report(report, Progress { done, total });
+    }
+
+    for worker in workers {
+        if let Err(e) = worker.join() {
+            panic::resume_unwind(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITIONS: &str = "\
+# Positions from the move generator tests
+rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1
+r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1
+
+8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1
+not a fen
+r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1
+rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8
+";
+
+    fn analyse(inputs: Vec<BoardInput>, threads: usize) -> Vec<(Report, Progress)> {
+        let mut reports = Vec::new();
+        analyse_all(inputs, threads, |report, progress| {
+            reports.push((report, progress))
+        });
+        reports
+    }
+
+    #[test]
+    fn test_read_fen_lines() {
+        let inputs = read_fen_lines(POSITIONS);
+
+        let names: Vec<&str> = inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["line 2", "line 3", "line 5", "line 6", "line 7", "line 8"]
+        );
+        assert_eq!(inputs[3].board.as_ref().err(), Some(&ChessError::FEN));
+    }
+
+    #[test]
+    fn test_read_table_files() {
+        let paths = vec![
+            "test/table1.txt".to_string(),
+            "test/missing.txt".to_string(),
+        ];
+        let inputs = read_table_files(&paths);
+
+        assert!(inputs[0].board.is_ok());
+        assert_eq!(
+            inputs[1].board.as_ref().err(),
+            Some(&ChessError::FILE_READING)
+        );
+    }
+
+    #[test]
+    fn test_reports_come_in_input_order() {
+        let reports = analyse(read_fen_lines(&POSITIONS.repeat(20)), 4);
+
+        assert_eq!(reports.len(), 120);
+        for (i, (report, progress)) in reports.iter().enumerate() {
+            assert_eq!(report.index, i);
+            assert_eq!(
+                *progress,
+                Progress {
+                    done: i + 1,
+                    total: 120
+                }
+            );
+        }
+        let moves: Vec<Option<usize>> = reports[..6]
+            .iter()
+            .map(|(report, _)| report.analysis.as_ref().ok().and_then(|a| a.legal_moves))
+            .collect();
+        assert_eq!(
+            moves,
+            vec![Some(20), Some(48), Some(14), None, Some(6), Some(44)]
+        );
+        assert_eq!(
+            reports[3].0.to_string(),
+            "line 6: ERROR: [FEN string is not valid]"
+        );
+    }
+
+    #[test]
+    fn test_same_reports_on_any_number_of_threads() {
+        let lines = |threads| -> Vec<String> {
+            analyse(read_fen_lines(POSITIONS), threads)
+                .iter()
+                .map(|(report, _)| report.to_string())
+                .collect()
+        };
+
+        assert_eq!(lines(1), lines(8));
+        assert_eq!(lines(0), lines(3));
+    }
+
+    #[test]
+    fn test_failed_analysis_is_reported() {
+        fn analyse_or_panic(board: &Board) -> Analysis {
+            if board.to_fen().starts_with("8/2p5") {
+                panic!("analysis failed");
+            }
+            Analysis::new(board)
+        }
+
+        let mut reports = Vec::new();
+        analyse_all_with(
+            read_fen_lines(POSITIONS),
+            2,
+            analyse_or_panic,
+            |report, _| reports.push(report),
+        );
+
+        assert_eq!(reports.len(), 6);
+        assert_eq!(
+            reports[2].analysis.as_ref().err(),
+            Some(&ChessError::ANALYSIS)
+        );
+        assert!(reports[4].analysis.is_ok());
+        assert!(reports[5].analysis.is_ok());
+    }
+
+    #[test]
+    fn test_analyse_nothing() {
+        assert!(analyse(Vec::new(), 4).is_empty());
+    }
+}
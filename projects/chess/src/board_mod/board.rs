@@ -0,0 +1,376 @@
+use crate::board_mod::board_builder::BoardBuilder;
+use crate::board_mod::castling_rights::CastlingRights;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::errors::position_error::PositionError;
+use crate::exchange_mod::static_exchange::StaticExchange;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::position_mod::position::Position;
+use crate::results::fight_result::FightResult;
+use crate::utils::checks::{pieces_pass_checks, position_pass_checks};
+use crate::utils::fen::{parse_fen, to_fen};
+use crate::utils::parsing::parse_board;
+
+/// Represents a chess table with its pieces. Every piece is inside the 8x8 table and no two pieces share a square.
+/// It also keeps the state of the game needed to continue it: whose turn it is, the castling rights,
+/// the en passant square and the move counters.
+/// This is the entry point for using the chess logic as a library.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "BoardRecord"))]
+pub struct Board {
+    pieces: PieceList,
+    side_to_move: Color,
+    castling: CastlingRights,
+    en_passant: Option<Position>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+/// Deserialized Board before its validation.
+/// The game state is optional so boards saved before it existed can still be read.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BoardRecord {
+    pieces: PieceList,
+    #[serde(default = "white")]
+    side_to_move: Color,
+    #[serde(default)]
+    castling: CastlingRights,
+    #[serde(default)]
+    en_passant: Option<Position>,
+    #[serde(default)]
+    halfmove_clock: u32,
+    #[serde(default = "first_move")]
+    fullmove_number: u32,
+}
+
+#[cfg(feature = "serde")]
+fn white() -> Color {
+    Color::White
+}
+
+#[cfg(feature = "serde")]
+fn first_move() -> u32 {
+    1
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardRecord> for Board {
+    type Error = ChessError<'static>;
+
+    fn try_from(record: BoardRecord) -> Result<Self, Self::Error> {
+        let mut board = Board::from_pieces(record.pieces)?;
+        board.side_to_move = record.side_to_move;
+        board.castling = record.castling;
+        board.en_passant = record.en_passant;
+        board.halfmove_clock = record.halfmove_clock;
+        board.fullmove_number = record.fullmove_number;
+        Ok(board)
+    }
+}
+
+impl Board {
+    /// Returns a builder to place pieces one by one.
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder::default()
+    }
+
+    /// Tries to create a Board from a list of pieces.
+    /// It fails if a piece is outside the table or if two pieces share a square.
+    /// White moves first, no castling is allowed and there is no en passant square.
+    pub fn from_pieces(pieces: PieceList) -> Result<Board, ChessError<'static>> {
+        for (i, piece) in pieces.iter().enumerate() {
+            if !piece.get_position().is_inside_table() {
+                return Err(ChessError::OUT_OF_BOUNDS);
+            }
+            let repeated = pieces
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.get_position() == piece.get_position());
+            if repeated {
+                return Err(ChessError::SQUARE_OCCUPIED);
+            }
+        }
+
+        Ok(Board::from_checked_pieces(pieces))
+    }
+
+    /// Creates a Board from a list of pieces already known to be inside the table and on different squares.
+    pub(crate) fn from_checked_pieces(pieces: PieceList) -> Board {
+        Board {
+            pieces,
+            side_to_move: Color::White,
+            castling: CastlingRights::default(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Sets the game state. Used by the builder and the FEN parser once the pieces are validated.
+    pub(crate) fn set_state(
+        &mut self,
+        side_to_move: Color,
+        castling: CastlingRights,
+        en_passant: Option<Position>,
+        halfmove_clock: u32,
+        fullmove_number: u32,
+    ) {
+        self.side_to_move = side_to_move;
+        self.castling = castling;
+        self.en_passant = en_passant;
+        self.halfmove_clock = halfmove_clock;
+        self.fullmove_number = fullmove_number;
+    }
+
+    /// Tries to create a Board from an 8x8 table in text format, one row per line.
+    pub fn from_table(table: &str) -> Result<Board, ChessError<'static>> {
+        parse_board(table)
+    }
+
+    /// Tries to create a Board from a FEN string, like
+    /// `rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1`.
+    /// Castling rights can be written as KQkq or, for Chess960, as the files of the rooks (Shredder-FEN).
+    pub fn from_fen(fen: &str) -> Result<Board, ChessError<'static>> {
+        parse_fen(fen)
+    }
+
+    /// Returns the position in FEN notation.
+    pub fn to_fen(&self) -> String {
+        to_fen(self)
+    }
+
+    /// Returns the position in the 8x8 table format read by `from_table`, one row per line.
+    pub fn to_table(&self) -> String {
+        let mut rows = Vec::new();
+        for y in (0..8).rev() {
+            let mut squares = Vec::new();
+            for x in 0..8 {
+                let square = match self.at(&Position { x, y }) {
+                    Some(piece) => piece.get_type().kind().table_name(piece.color),
+                    None => "_".to_string(),
+                };
+                squares.push(square);
+            }
+            rows.push(squares.join(" "));
+        }
+        rows.join("\n")
+    }
+
+    /// Returns the color that moves next
+    pub fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    /// Returns the castlings that are still allowed
+    pub fn castling(&self) -> CastlingRights {
+        self.castling
+    }
+
+    /// Returns the square a pawn can capture en passant into, if there is one
+    pub fn en_passant(&self) -> Option<Position> {
+        self.en_passant
+    }
+
+    /// Returns the number of half moves since the last capture or pawn move
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// Returns the number of the current full move. It starts at 1 and increases after Black moves.
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// Returns an iterator over every piece in the table.
+    pub fn pieces(&self) -> std::slice::Iter<'_, Piece> {
+        self.pieces.iter()
+    }
+
+    /// Returns an iterator over the pieces of the given color.
+    pub fn pieces_of(&self, color: Color) -> impl Iterator<Item = &Piece> {
+        self.pieces().filter(move |p| p.color == color)
+    }
+
+    /// Returns the piece at the given position, if there is one.
+    pub fn at(&self, position: &Position) -> Option<&Piece> {
+        self.pieces().find(|p| p.get_position() == position)
+    }
+
+    /// Returns the number of pieces in the table
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    /// Returns true if the table has no pieces
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// PieceList getter
+    pub fn piece_list(&self) -> &PieceList {
+        &self.pieces
+    }
+
+    /// Makes the two pieces of the table fight each other.
+    /// It fails if there are not exactly two pieces of different colors.
+    pub fn fight(&self) -> Result<FightResult<'_>, ChessError<'_>> {
+        pieces_pass_checks(&self.pieces).map_err(|e| *e)?;
+        Ok(self.pieces.fight())
+    }
+
+    /// Checks that the position could happen in a game, beyond the checks needed to build the Board:
+    /// kings, pawns, piece counts, castling rights, en passant square and checks.
+    pub fn validate(&self) -> Result<(), PositionError> {
+        position_pass_checks(&Mailbox::from_board(self))
+    }
+
+    /// Evaluates the capture sequence on the given square. Returns None if the square is empty.
+    pub fn static_exchange(&self, target: &Position) -> Option<StaticExchange<'_>> {
+        self.pieces.static_exchange(target)
+    }
+}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = &'a Piece;
+    type IntoIter = std::slice::Iter<'a, Piece>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pieces()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(x: i32, y: i32) -> Position {
+        Position::new(x, y).unwrap()
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let board = Board::builder().build().unwrap();
+
+        assert!(board.is_empty());
+        assert!(board.at(&position(0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_at() {
+        let board = Board::builder()
+            .place("R", position(3, 3))
+            .place("d", position(3, 4))
+            .build()
+            .unwrap();
+
+        let piece = board.at(&position(3, 4)).unwrap();
+
+        assert_eq!(piece.get_type().string(), "Queen");
+        assert_eq!(piece.color, Color::White);
+        assert!(board.at(&position(4, 4)).is_none());
+    }
+
+    #[test]
+    fn test_pieces_iterator() {
+        let board = Board::builder()
+            .place("R", position(3, 3))
+            .place("d", position(3, 4))
+            .place("p", position(0, 1))
+            .build()
+            .unwrap();
+
+        let names: Vec<&str> = board.pieces().map(|p| p.get_type().string()).collect();
+
+        assert_eq!(names, vec!["King", "Queen", "Pawn"]);
+        assert_eq!((&board).into_iter().count(), 3);
+        assert_eq!(board.pieces_of(Color::White).count(), 2);
+        assert_eq!(board.pieces_of(Color::Black).count(), 1);
+    }
+
+    #[test]
+    fn test_from_pieces_out_of_bounds() {
+        let mut pieces = PieceList::init();
+        pieces.push(Piece::new("R", 8, 3).unwrap());
+
+        assert_eq!(
+            Board::from_pieces(pieces).err(),
+            Some(ChessError::OUT_OF_BOUNDS)
+        );
+    }
+
+    #[test]
+    fn test_from_pieces_same_square() {
+        let mut pieces = PieceList::init();
+        pieces.push(Piece::new("R", 3, 3).unwrap());
+        pieces.push(Piece::new("d", 3, 3).unwrap());
+
+        assert_eq!(
+            Board::from_pieces(pieces).err(),
+            Some(ChessError::SQUARE_OCCUPIED)
+        );
+    }
+
+    #[test]
+    fn test_from_table() {
+        let table = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ A _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ t _ _ _ _";
+        let board = Board::from_table(table).unwrap();
+
+        assert_eq!(board.len(), 2);
+        assert_eq!(
+            board.at(&position(3, 3)).unwrap().get_type().string(),
+            "Bishop"
+        );
+        assert_eq!(
+            board.fight().unwrap().output,
+            FightResult::WHITE_WINS.output
+        );
+    }
+
+    #[test]
+    fn test_to_table_round_trip() {
+        let table = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ A _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ t _ _ _ _";
+        let board = Board::from_table(table).unwrap();
+
+        assert_eq!(board.to_table(), table);
+    }
+
+    #[test]
+    fn test_default_state() {
+        let board = Board::builder().place("R", position(3, 3)).build().unwrap();
+
+        assert_eq!(board.side_to_move(), Color::White);
+        assert!(board.castling().is_empty());
+        assert_eq!(board.en_passant(), None);
+        assert_eq!(board.fullmove_number(), 1);
+    }
+
+    #[test]
+    fn test_fight_needs_two_pieces() {
+        let board = Board::builder().place("R", position(3, 3)).build().unwrap();
+
+        assert_eq!(board.fight().err(), Some(ChessError::NUMBER_PIECES));
+    }
+
+    #[test]
+    fn test_fight_needs_different_colors() {
+        let board = Board::builder()
+            .place("R", position(3, 3))
+            .place("D", position(3, 4))
+            .build()
+            .unwrap();
+
+        assert_eq!(board.fight().err(), Some(ChessError::COLOR));
+    }
+
+    #[test]
+    fn test_board_can_be_sent_between_threads() {
+        let board = Board::from_table(include_str!("../../test/table1.txt")).unwrap();
+        let fen = board.to_fen();
+
+        let handle = std::thread::spawn(move || board.to_fen());
+        assert_eq!(handle.join().unwrap(), fen);
+    }
+}
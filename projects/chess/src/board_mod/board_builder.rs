@@ -0,0 +1,138 @@
+use crate::board_mod::board::Board;
+use crate::board_mod::castling_rights::CastlingRights;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::position_mod::position::Position;
+
+/// Builds a Board by placing pieces one by one.
+/// Errors are kept until `build` is called, so placements can be chained.
+/// The game state can be set too; by default White moves first and no castling is allowed.
+pub struct BoardBuilder {
+    pieces: PieceList,
+    error: Option<ChessError<'static>>,
+    side_to_move: Color,
+    castling: CastlingRights,
+    en_passant: Option<Position>,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        BoardBuilder {
+            pieces: PieceList::default(),
+            error: None,
+            side_to_move: Color::White,
+            castling: CastlingRights::default(),
+            en_passant: None,
+        }
+    }
+}
+
+impl BoardBuilder {
+    /// Places a piece, given by its name in the table format, in the given position.
+    pub fn place(mut self, name: &str, position: Position) -> BoardBuilder {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match Piece::new(name, position.x, position.y) {
+            Some(piece) => self.pieces.push(piece),
+            None => self.error = Some(ChessError::UKNOWN),
+        }
+        self
+    }
+
+    /// Places an already created piece.
+    pub fn place_piece(mut self, piece: Piece) -> BoardBuilder {
+        self.pieces.push(piece);
+        self
+    }
+
+    /// Sets the color that moves next.
+    pub fn side_to_move(mut self, color: Color) -> BoardBuilder {
+        self.side_to_move = color;
+        self
+    }
+
+    /// Sets the castlings that are still allowed.
+    pub fn castling(mut self, castling: CastlingRights) -> BoardBuilder {
+        self.castling = castling;
+        self
+    }
+
+    /// Sets the square a pawn can capture en passant into.
+    pub fn en_passant(mut self, square: Option<Position>) -> BoardBuilder {
+        self.en_passant = square;
+        self
+    }
+
+    /// Tries to create the Board. It fails if a piece was unknown, outside the table, or on an occupied square.
+    pub fn build(self) -> Result<Board, ChessError<'static>> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let mut board = Board::from_pieces(self.pieces)?;
+        board.set_state(self.side_to_move, self.castling, self.en_passant, 0, 1);
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let board = BoardBuilder::default()
+            .place("R", Position { x: 3, y: 3 })
+            .place_piece(Piece::new("t", 0, 0).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(board.len(), 2);
+    }
+
+    #[test]
+    fn test_build_with_state() {
+        let board = BoardBuilder::default()
+            .place("r", Position { x: 4, y: 0 })
+            .place("t", Position { x: 7, y: 0 })
+            .side_to_move(Color::Black)
+            .castling(CastlingRights::classical())
+            .build()
+            .unwrap();
+
+        assert_eq!(board.side_to_move(), Color::Black);
+        assert_eq!(board.castling(), CastlingRights::classical());
+    }
+
+    #[test]
+    fn test_build_unknown_piece() {
+        let board = BoardBuilder::default()
+            .place("X", Position { x: 3, y: 3 })
+            .place("R", Position { x: 4, y: 4 })
+            .build();
+
+        assert_eq!(board.err(), Some(ChessError::UKNOWN));
+    }
+
+    #[test]
+    fn test_build_out_of_bounds() {
+        let board = BoardBuilder::default()
+            .place("R", Position { x: 3, y: 9 })
+            .build();
+
+        assert_eq!(board.err(), Some(ChessError::OUT_OF_BOUNDS));
+    }
+
+    #[test]
+    fn test_build_occupied_square() {
+        let board = BoardBuilder::default()
+            .place("R", Position { x: 3, y: 3 })
+            .place("r", Position { x: 3, y: 3 })
+            .build();
+
+        assert_eq!(board.err(), Some(ChessError::SQUARE_OCCUPIED));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::color::Color;
+
+/// Represents the side of the board a king castles to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CastlingSide {
+    King,
+    Queen,
+}
+
+impl CastlingSide {
+    pub const ALL: [CastlingSide; 2] = [CastlingSide::King, CastlingSide::Queen];
+
+    /// Returns the file where the king ends after castling, for both classical chess and Chess960.
+    pub fn king_destination(&self) -> i32 {
+        match self {
+            CastlingSide::King => 6,
+            CastlingSide::Queen => 2,
+        }
+    }
+
+    /// Returns the file where the rook ends after castling, for both classical chess and Chess960.
+    pub fn rook_destination(&self) -> i32 {
+        match self {
+            CastlingSide::King => 5,
+            CastlingSide::Queen => 3,
+        }
+    }
+}
+
+/// Represents the castlings that are still allowed.
+/// Each right keeps the file of the rook that castles, so Chess960 layouts, where rooks do not
+/// start on the corners, are supported as well as the classical one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CastlingRights {
+    white_king_side: Option<i32>,
+    white_queen_side: Option<i32>,
+    black_king_side: Option<i32>,
+    black_queen_side: Option<i32>,
+}
+
+impl CastlingRights {
+    /// Returns the rights of the classical starting position: rooks on the a and h files.
+    pub fn classical() -> CastlingRights {
+        CastlingRights {
+            white_king_side: Some(7),
+            white_queen_side: Some(0),
+            black_king_side: Some(7),
+            black_queen_side: Some(0),
+        }
+    }
+
+    /// Returns the file of the rook that can castle on the given side, if the right is kept.
+    pub fn get(&self, color: Color, side: CastlingSide) -> Option<i32> {
+        match (color, side) {
+            (Color::White, CastlingSide::King) => self.white_king_side,
+            (Color::White, CastlingSide::Queen) => self.white_queen_side,
+            (Color::Black, CastlingSide::King) => self.black_king_side,
+            (Color::Black, CastlingSide::Queen) => self.black_queen_side,
+        }
+    }
+
+    /// Sets the file of the rook that can castle on the given side. None removes the right.
+    pub fn set(&mut self, color: Color, side: CastlingSide, rook_file: Option<i32>) {
+        let right = match (color, side) {
+            (Color::White, CastlingSide::King) => &mut self.white_king_side,
+            (Color::White, CastlingSide::Queen) => &mut self.white_queen_side,
+            (Color::Black, CastlingSide::King) => &mut self.black_king_side,
+            (Color::Black, CastlingSide::Queen) => &mut self.black_queen_side,
+        };
+        *right = rook_file;
+    }
+
+    /// Removes both rights of the given color. This happens when its king moves.
+    pub fn remove_color(&mut self, color: Color) {
+        for side in CastlingSide::ALL {
+            self.set(color, side, None);
+        }
+    }
+
+    /// Removes the right tied to the rook standing on the given file of the color's back rank.
+    /// This happens when that rook moves or is captured.
+    pub fn remove_rook(&mut self, color: Color, file: i32) {
+        for side in CastlingSide::ALL {
+            if self.get(color, side) == Some(file) {
+                self.set(color, side, None);
+            }
+        }
+    }
+
+    /// Returns true if no castling is allowed anymore.
+    pub fn is_empty(&self) -> bool {
+        *self == CastlingRights::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_empty() {
+        assert!(CastlingRights::default().is_empty());
+        assert!(!CastlingRights::classical().is_empty());
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut rights = CastlingRights::default();
+        rights.set(Color::Black, CastlingSide::Queen, Some(1));
+
+        assert_eq!(rights.get(Color::Black, CastlingSide::Queen), Some(1));
+        assert_eq!(rights.get(Color::White, CastlingSide::Queen), None);
+    }
+
+    #[test]
+    fn test_remove_color() {
+        let mut rights = CastlingRights::classical();
+        rights.remove_color(Color::White);
+
+        assert_eq!(rights.get(Color::White, CastlingSide::King), None);
+        assert_eq!(rights.get(Color::Black, CastlingSide::King), Some(7));
+    }
+
+    #[test]
+    fn test_remove_rook() {
+        let mut rights = CastlingRights::classical();
+        rights.remove_rook(Color::Black, 0);
+
+        assert_eq!(rights.get(Color::Black, CastlingSide::Queen), None);
+        assert_eq!(rights.get(Color::Black, CastlingSide::King), Some(7));
+        assert_eq!(rights.get(Color::White, CastlingSide::Queen), Some(0));
+    }
+
+    #[test]
+    fn test_destinations() {
+        assert_eq!(CastlingSide::King.king_destination(), 6);
+        assert_eq!(CastlingSide::Queen.rook_destination(), 3);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod board;
+pub mod board_builder;
+pub mod castling_rights;
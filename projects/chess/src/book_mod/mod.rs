@@ -0,0 +1,3 @@
+pub mod polyglot;
+pub mod random64;
+pub mod zobrist;
@@ -0,0 +1,303 @@
+use crate::book_mod::zobrist::polyglot_key;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+use std::fs;
+use std::path::Path;
+
+/// Size in bytes of each entry of a Polyglot book.
+const ENTRY_SIZE: usize = 16;
+
+/// Promotions in the order Polyglot numbers them, starting at 1.
+const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+];
+
+/// Represents an entry of a Polyglot book: a move that can be played in the position with the key,
+/// and how good it is compared to the other moves of that position.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BookEntry {
+    pub key: u64,
+    pub raw_move: u16,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+/// Represents a move of the book for the current position, with its weight.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BookMove {
+    pub chess_move: Move,
+    pub weight: u16,
+}
+
+/// Represents an opening book in the Polyglot `.bin` format: entries of 16 bytes in big-endian,
+/// sorted by key.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Book {
+    entries: Vec<BookEntry>,
+}
+
+impl BookEntry {
+    /// Creates the entry of a move of the position.
+    pub fn new(position: &Mailbox, chess_move: &Move, weight: u16) -> BookEntry {
+        BookEntry {
+            key: polyglot_key(position),
+            raw_move: encode_move(position, chess_move),
+            weight,
+            learn: 0,
+        }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> BookEntry {
+        let mut key = [0u8; 8];
+        key.copy_from_slice(&bytes[0..8]);
+        let mut learn = [0u8; 4];
+        learn.copy_from_slice(&bytes[12..16]);
+        BookEntry {
+            key: u64::from_be_bytes(key),
+            raw_move: u16::from_be_bytes([bytes[8], bytes[9]]),
+            weight: u16::from_be_bytes([bytes[10], bytes[11]]),
+            learn: u32::from_be_bytes(learn),
+        }
+    }
+
+    fn to_bytes(self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0u8; ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.raw_move.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+impl Book {
+    /// Creates a book from its entries, in any order.
+    pub fn from_entries(mut entries: Vec<BookEntry>) -> Book {
+        entries.sort_by_key(|e| e.key);
+        Book { entries }
+    }
+
+    /// Reads a book from the bytes of a `.bin` file.
+    /// It fails if the size is not a multiple of an entry or if the entries are not sorted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Book, ChessError<'static>> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(ChessError::BOOK_FILE);
+        }
+        let entries: Vec<BookEntry> = bytes
+            .chunks(ENTRY_SIZE)
+            .map(BookEntry::from_bytes)
+            .collect();
+        if entries.windows(2).any(|pair| pair[0].key > pair[1].key) {
+            return Err(ChessError::BOOK_FILE);
+        }
+        Ok(Book { entries })
+    }
+
+    /// Reads a book from a `.bin` file.
+    pub fn load(path: &Path) -> Result<Book, ChessError<'static>> {
+        let bytes = fs::read(path).map_err(|_| ChessError::BOOK_FILE)?;
+        Book::from_bytes(&bytes)
+    }
+
+    /// Returns the book in the `.bin` format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.entries.iter().flat_map(|e| e.to_bytes()).collect()
+    }
+
+    /// Returns the number of entries of the book.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the book has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the legal moves the book has for the position, the heaviest first.
+    /// Entries whose move is not legal in the position are left out.
+    pub fn moves(&self, position: &Mailbox) -> Vec<BookMove> {
+        let key = polyglot_key(position);
+        let start = self.entries.partition_point(|e| e.key < key);
+        let mut moves: Vec<BookMove> = self.entries[start..]
+            .iter()
+            .take_while(|e| e.key == key)
+            .filter_map(|e| {
+                decode_move(position, e.raw_move).map(|chess_move| BookMove {
+                    chess_move,
+                    weight: e.weight,
+                })
+            })
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        moves
+    }
+
+    /// Returns the heaviest move of the book for the position, if there is any.
+    pub fn best_move(&self, position: &Mailbox) -> Option<Move> {
+        self.moves(position).first().map(|m| m.chess_move)
+    }
+}
+
+/// Returns the move as Polyglot writes it. Castling is written as the king capturing its own rook.
+fn encode_move(position: &Mailbox, chess_move: &Move) -> u16 {
+    let mut to = chess_move.to;
+    if let Some(side) = chess_move.castling {
+        let color = position.side_to_move;
+        if let Some(file) = position.castling.get(color, side) {
+            to = Position {
+                x: file,
+                y: color.back_rank(),
+            };
+        }
+    }
+    let promotion = chess_move
+        .promotion
+        .and_then(|kind| PROMOTIONS.iter().position(|p| *p == kind))
+        .map_or(0, |i| i + 1);
+
+    (to.x as u16)
+        | (to.y as u16) << 3
+        | (chess_move.from.x as u16) << 6
+        | (chess_move.from.y as u16) << 9
+        | (promotion as u16) << 12
+}
+
+/// Returns the legal move of the position written in the Polyglot format, if there is one.
+fn decode_move(position: &Mailbox, raw_move: u16) -> Option<Move> {
+    let square = |shift: u16| Position {
+        x: ((raw_move >> shift) & 7) as i32,
+        y: ((raw_move >> (shift + 3)) & 7) as i32,
+    };
+    let mut uci = format!("{}{}", square(6), square(0));
+    match (raw_move >> 12) & 7 {
+        0 => {}
+        n => uci.push(PROMOTIONS.get(n as usize - 1)?.fen_char(Color::Black)),
+    }
+    position.find_move(&uci)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+    use crate::board_mod::castling_rights::CastlingSide;
+
+    fn position(fen: &str) -> Mailbox {
+        Mailbox::from_board(&Board::from_fen(fen).unwrap())
+    }
+
+    fn start() -> Mailbox {
+        position("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+    }
+
+    fn entry(position: &Mailbox, uci: &str, weight: u16) -> BookEntry {
+        BookEntry::new(position, &position.find_move(uci).unwrap(), weight)
+    }
+
+    #[test]
+    fn test_moves_by_weight() {
+        let start = start();
+        let after_e4 = start.play(&start.find_move("e2e4").unwrap());
+        let book = Book::from_entries(vec![
+            entry(&after_e4, "c7c5", 40),
+            entry(&start, "d2d4", 30),
+            entry(&start, "e2e4", 50),
+            entry(&start, "g1f3", 5),
+        ]);
+
+        let moves: Vec<(String, u16)> = book
+            .moves(&start)
+            .iter()
+            .map(|m| (m.chess_move.to_uci(), m.weight))
+            .collect();
+
+        assert_eq!(
+            moves,
+            vec![
+                ("e2e4".to_string(), 50),
+                ("d2d4".to_string(), 30),
+                ("g1f3".to_string(), 5)
+            ]
+        );
+        assert_eq!(book.best_move(&after_e4).unwrap().to_uci(), "c7c5");
+    }
+
+    #[test]
+    fn test_position_not_in_book() {
+        let start = start();
+        let book = Book::from_entries(vec![entry(&start, "e2e4", 1)]);
+
+        assert!(book
+            .moves(&position("8/8/3k4/8/8/2R5/8/4K3 b - - 0 1"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_encode_move() {
+        let start = start();
+
+        // e2e4: to e4 (file 4, row 3), from e2 (file 4, row 1).
+        assert_eq!(
+            entry(&start, "e2e4", 1).raw_move,
+            4 | 3 << 3 | 4 << 6 | 1 << 9
+        );
+    }
+
+    #[test]
+    fn test_castling_is_written_as_king_takes_rook() {
+        let position = position("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let castle = entry(&position, "e1g1", 1);
+
+        // e1h1
+        assert_eq!(castle.raw_move, 7 | 4 << 6);
+
+        let book = Book::from_entries(vec![castle]);
+        let chess_move = book.best_move(&position).unwrap();
+        assert_eq!(chess_move.castling, Some(CastlingSide::King));
+        assert_eq!(chess_move.to_uci(), "e1g1");
+    }
+
+    #[test]
+    fn test_promotion() {
+        let position = position("8/1P5k/8/8/8/8/8/K7 w - - 0 1");
+        let book = Book::from_entries(vec![entry(&position, "b7b8n", 1)]);
+
+        assert_eq!(book.best_move(&position).unwrap().to_uci(), "b7b8n");
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let start = start();
+        let book = Book::from_entries(vec![entry(&start, "e2e4", 50), entry(&start, "d2d4", 30)]);
+        let bytes = book.to_bytes();
+
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[0..8], &0x463b96181691fc9cu64.to_be_bytes());
+        assert_eq!(Book::from_bytes(&bytes), Ok(book));
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        assert_eq!(Book::from_bytes(&[0; 15]), Err(ChessError::BOOK_FILE));
+
+        let mut unsorted = vec![0xff; 16];
+        unsorted.extend_from_slice(&[0; 16]);
+        assert_eq!(Book::from_bytes(&unsorted), Err(ChessError::BOOK_FILE));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert_eq!(
+            Book::load(Path::new("does/not/exist.bin")),
+            Err(ChessError::BOOK_FILE)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::board_mod::castling_rights::CastlingSide;
+use crate::book_mod::random64::RANDOM64;
+use crate::color::Color;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+
+const CASTLING_OFFSET: usize = 768;
+const EN_PASSANT_OFFSET: usize = 772;
+const TURN_OFFSET: usize = 780;
+
+/// Returns the Zobrist key of the position as the Polyglot opening book format computes it,
+/// so positions can be looked up in Polyglot books.
+pub fn polyglot_key(position: &Mailbox) -> u64 {
+    let mut key = 0;
+
+    for (square, color, kind) in position.pieces() {
+        let kind_index = 2 * role_index(kind)
+            + match color {
+                Color::Black => 0,
+                Color::White => 1,
+            };
+        key ^= RANDOM64[64 * kind_index + square.index()];
+    }
+
+    let rights = [
+        (Color::White, CastlingSide::King),
+        (Color::White, CastlingSide::Queen),
+        (Color::Black, CastlingSide::King),
+        (Color::Black, CastlingSide::Queen),
+    ];
+    for (i, (color, side)) in rights.iter().enumerate() {
+        if position.castling.get(*color, *side).is_some() {
+            key ^= RANDOM64[CASTLING_OFFSET + i];
+        }
+    }
+
+    if let Some(square) = position.en_passant {
+        if can_capture_en_passant(position, &square) {
+            key ^= RANDOM64[EN_PASSANT_OFFSET + square.x as usize];
+        }
+    }
+
+    if position.side_to_move == Color::White {
+        key ^= RANDOM64[TURN_OFFSET];
+    }
+    key
+}
+
+/// Returns the order Polyglot gives to each kind of piece.
+fn role_index(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::Pawn => 0,
+        PieceKind::Knight => 1,
+        PieceKind::Bishop => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    }
+}
+
+/// Polyglot only counts the en passant square if a pawn of the side to move stands next to the
+/// pawn that just moved two squares.
+fn can_capture_en_passant(position: &Mailbox, square: &Position) -> bool {
+    let side = position.side_to_move;
+    [-1, 1].iter().any(|dx| {
+        square
+            .offset(*dx, -side.pawn_direction())
+            .and_then(|p| position.get(&p))
+            == Some((side, PieceKind::Pawn))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+
+    fn key(fen: &str) -> u64 {
+        polyglot_key(&Mailbox::from_board(&Board::from_fen(fen).unwrap()))
+    }
+
+    // Keys given as examples by the Polyglot book format specification.
+
+    #[test]
+    fn test_start_position() {
+        assert_eq!(
+            key("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+            0x463b96181691fc9c
+        );
+    }
+
+    #[test]
+    fn test_after_moves() {
+        assert_eq!(
+            key("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"),
+            0x823c9b50fd114196
+        );
+        assert_eq!(
+            key("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2"),
+            0x0756b94461c50fb0
+        );
+        assert_eq!(
+            key("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2"),
+            0x662fafb965db29d4
+        );
+    }
+
+    #[test]
+    fn test_en_passant_that_can_be_captured() {
+        assert_eq!(
+            key("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"),
+            0x22a48b5a8e47ff78
+        );
+    }
+
+    #[test]
+    fn test_lost_castling_rights() {
+        assert_eq!(
+            key("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4"),
+            0x00fdd303c946bdd9
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use crate::board_mod::board::Board;
+use crate::board_mod::castling_rights::{CastlingRights, CastlingSide};
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+
+/// Number of Chess960 (Fischer Random) starting positions.
+pub const CHESS960_POSITIONS: u32 = 960;
+
+/// Index of the classical starting position in the Chess960 numbering.
+pub const CLASSICAL_INDEX: u32 = 518;
+
+/// Squares, among the five left after placing bishops and queen, taken by the two knights.
+const KNIGHT_PAIRS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Returns the back rank of the Chess960 starting position with the given index, from file a to h.
+/// It follows Scharnagl's numbering, where 518 is the classical RNBQKBNR.
+/// Returns None if the index is not between 0 and 959.
+pub fn back_rank(index: u32) -> Option<[PieceKind; 8]> {
+    if index >= CHESS960_POSITIONS {
+        return None;
+    }
+
+    let mut rank: [Option<PieceKind>; 8] = [None; 8];
+    let mut n = index as usize;
+
+    rank[2 * (n % 4) + 1] = Some(PieceKind::Bishop);
+    n /= 4;
+    rank[2 * (n % 4)] = Some(PieceKind::Bishop);
+    n /= 4;
+    place_on_empty(&mut rank, n % 6, PieceKind::Queen);
+    n /= 6;
+    let (first, second) = KNIGHT_PAIRS[n];
+    // The second knight is placed first so the index of the first one is not shifted.
+    place_on_empty(&mut rank, second, PieceKind::Knight);
+    place_on_empty(&mut rank, first, PieceKind::Knight);
+    for kind in [PieceKind::Rook, PieceKind::King, PieceKind::Rook] {
+        place_on_empty(&mut rank, 0, kind);
+    }
+
+    let mut result = [PieceKind::Pawn; 8];
+    for (square, kind) in result.iter_mut().zip(rank) {
+        *square = kind?;
+    }
+    Some(result)
+}
+
+/// Returns the index of the given back rank, or None if it is not a Chess960 starting layout.
+pub fn index_of(rank: &[PieceKind; 8]) -> Option<u32> {
+    (0..CHESS960_POSITIONS).find(|i| back_rank(*i).as_ref() == Some(rank))
+}
+
+/// Returns the Board of the Chess960 starting position with the given index.
+/// Every piece starts on its usual rank, White moves first and both sides can castle with both rooks.
+pub fn chess960_board(index: u32) -> Result<Board, ChessError<'static>> {
+    let rank = back_rank(index).ok_or(ChessError::CHESS960_INDEX)?;
+
+    let king = rank
+        .iter()
+        .position(|k| *k == PieceKind::King)
+        .ok_or(ChessError::CHESS960_INDEX)? as i32;
+    let mut pieces = PieceList::default();
+    let mut castling = CastlingRights::default();
+    for (color, first_rank, pawn_rank) in [(Color::White, 0, 1), (Color::Black, 7, 6)] {
+        for (x, kind) in rank.iter().enumerate() {
+            let x = x as i32;
+            pieces.push(Piece::from_kind(
+                *kind,
+                color,
+                Position { x, y: first_rank },
+            ));
+            pieces.push(Piece::from_kind(
+                PieceKind::Pawn,
+                color,
+                Position { x, y: pawn_rank },
+            ));
+        }
+        for (x, kind) in rank.iter().enumerate() {
+            if *kind == PieceKind::Rook {
+                let side = if x as i32 > king {
+                    CastlingSide::King
+                } else {
+                    CastlingSide::Queen
+                };
+                castling.set(color, side, Some(x as i32));
+            }
+        }
+    }
+
+    let mut board = Board::from_pieces(pieces)?;
+    board.set_state(Color::White, castling, None, 0, 1);
+    Ok(board)
+}
+
+/// Places the piece on the n-th empty square of the rank, counting from file a.
+fn place_on_empty(rank: &mut [Option<PieceKind>; 8], n: usize, kind: PieceKind) {
+    if let Some(square) = rank.iter_mut().filter(|s| s.is_none()).nth(n) {
+        *square = Some(kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files_of(rank: &[PieceKind; 8], kind: PieceKind) -> Vec<usize> {
+        (0..8).filter(|x| rank[*x] == kind).collect()
+    }
+
+    #[test]
+    fn test_every_layout_is_valid() {
+        let mut seen = std::collections::HashSet::new();
+
+        for index in 0..CHESS960_POSITIONS {
+            let rank = back_rank(index).unwrap();
+            let bishops = files_of(&rank, PieceKind::Bishop);
+            let rooks = files_of(&rank, PieceKind::Rook);
+            let king = files_of(&rank, PieceKind::King);
+
+            assert_eq!(bishops.len(), 2);
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            assert_eq!(rooks.len(), 2);
+            assert_eq!(king.len(), 1);
+            assert!(rooks[0] < king[0] && king[0] < rooks[1]);
+            assert_eq!(files_of(&rank, PieceKind::Queen).len(), 1);
+            assert_eq!(files_of(&rank, PieceKind::Knight).len(), 2);
+            assert!(seen.insert(rank));
+        }
+
+        assert_eq!(seen.len(), 960);
+    }
+
+    #[test]
+    fn test_classical_index() {
+        let board = chess960_board(CLASSICAL_INDEX).unwrap();
+
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(board.castling(), CastlingRights::classical());
+    }
+
+    #[test]
+    fn test_known_layouts() {
+        assert_eq!(
+            chess960_board(0).unwrap().to_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+        assert_eq!(
+            chess960_board(959).unwrap().to_fen(),
+            "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_index_of() {
+        for index in [0, 1, 200, CLASSICAL_INDEX, 959] {
+            assert_eq!(index_of(&back_rank(index).unwrap()), Some(index));
+        }
+        assert_eq!(index_of(&[PieceKind::Rook; 8]), None);
+    }
+
+    #[test]
+    fn test_exports_round_trip() {
+        for index in 0..CHESS960_POSITIONS {
+            let board = chess960_board(index).unwrap();
+            let from_fen = Board::from_fen(&board.to_fen()).unwrap();
+            let from_table = Board::from_table(&board.to_table()).unwrap();
+
+            assert_eq!(board.len(), 32);
+            assert_eq!(from_fen.to_fen(), board.to_fen());
+            assert_eq!(from_fen.castling(), board.castling());
+            assert_eq!(from_table.to_table(), board.to_table());
+        }
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        assert_eq!(back_rank(960), None);
+        assert_eq!(chess960_board(960).err(), Some(ChessError::CHESS960_INDEX));
+    }
+}
@@ -1,8 +1,58 @@
 use core::fmt::Debug;
 
 /// Represents a color in the chess table: black or white
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     Black,
     White,
 }
+
+impl Color {
+    /// Returns the color of the other side
+    pub fn opposite(&self) -> Color {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+
+    /// Returns the rank where the pieces of this color start: 0 for white, 7 for black
+    pub fn back_rank(&self) -> i32 {
+        match self {
+            Color::Black => 7,
+            Color::White => 0,
+        }
+    }
+
+    /// Returns the direction this color's pawns advance in: up for white, down for black
+    pub fn pawn_direction(&self) -> i32 {
+        match self {
+            Color::Black => -1,
+            Color::White => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opposite() {
+        assert_eq!(Color::Black.opposite(), Color::White);
+        assert_eq!(Color::White.opposite(), Color::Black);
+    }
+
+    #[test]
+    fn test_back_rank() {
+        assert_eq!(Color::White.back_rank(), 0);
+        assert_eq!(Color::Black.back_rank(), 7);
+    }
+
+    #[test]
+    fn test_pawn_direction() {
+        assert_eq!(Color::White.pawn_direction(), 1);
+        assert_eq!(Color::Black.pawn_direction(), -1);
+    }
+}
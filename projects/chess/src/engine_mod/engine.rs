@@ -0,0 +1,233 @@
+use crate::board_mod::board::Board;
+use crate::book_mod::polyglot::Book;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::tablebase_mod::tablebase::Tablebases;
+
+/// Score of being mated right now. Mates found further away score a bit less.
+const MATE: i32 = 100_000;
+
+/// Tells where the move chosen by the engine came from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MoveSource {
+    Book,
+    Tablebase,
+    Search,
+}
+
+/// Represents the move chosen by the engine.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EngineMove {
+    pub chess_move: Move,
+    pub source: MoveSource,
+}
+
+/// Represents a simple engine. It plays from the opening book while the position is in it,
+/// then from the tablebases, and otherwise searches a few plies counting material.
+pub struct Engine {
+    book: Option<Book>,
+    tablebases: Tablebases,
+    depth: u32,
+}
+
+impl Engine {
+    /// Creates an engine that searches `depth` plies, without a book or tablebases.
+    pub fn new(depth: u32) -> Engine {
+        Engine {
+            book: None,
+            tablebases: Tablebases::default(),
+            depth: depth.max(1),
+        }
+    }
+
+    /// Adds the opening book the engine plays from first.
+    pub fn with_book(mut self, book: Book) -> Engine {
+        self.book = Some(book);
+        self
+    }
+
+    /// Adds the tablebases the engine plays from in the endings they cover.
+    pub fn with_tablebases(mut self, tablebases: Tablebases) -> Engine {
+        self.tablebases = tablebases;
+        self
+    }
+
+    /// Returns the move to play in the Board, or None if the game is over.
+    pub fn choose_move(&self, board: &Board) -> Option<EngineMove> {
+        let position = Mailbox::from_board(board);
+
+        if let Some(chess_move) = self.book.as_ref().and_then(|b| b.best_move(&position)) {
+            return Some(EngineMove {
+                chess_move,
+                source: MoveSource::Book,
+            });
+        }
+        if let Some(chess_move) = self.tablebases.best_move(board) {
+            return Some(EngineMove {
+                chess_move,
+                source: MoveSource::Tablebase,
+            });
+        }
+
+        let mut best: Option<(i32, Move)> = None;
+        let mut alpha = -MATE - 1;
+        for chess_move in ordered_moves(&position) {
+            let score = -search(
+                &position.play(&chess_move),
+                self.depth - 1,
+                1,
+                -MATE - 1,
+                -alpha,
+            );
+            if best.is_none_or(|(s, _)| score > s) {
+                best = Some((score, chess_move));
+                alpha = alpha.max(score);
+            }
+        }
+        best.map(|(_, chess_move)| EngineMove {
+            chess_move,
+            source: MoveSource::Search,
+        })
+    }
+}
+
+/// Returns the score of the position for the side to move, searching `depth` plies with alpha-beta.
+fn search(position: &Mailbox, depth: u32, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+    let moves = ordered_moves(position);
+    if moves.is_empty() {
+        return match position.in_check(position.side_to_move) {
+            true => ply - MATE,
+            false => 0,
+        };
+    }
+    if depth == 0 {
+        return evaluate(position);
+    }
+
+    for chess_move in moves {
+        let score = -search(
+            &position.play(&chess_move),
+            depth - 1,
+            ply + 1,
+            -beta,
+            -alpha,
+        );
+        if score >= beta {
+            return score;
+        }
+        alpha = alpha.max(score);
+    }
+    alpha
+}
+
+/// Returns the legal moves of the position, captures and promotions first so alpha-beta cuts more.
+fn ordered_moves(position: &Mailbox) -> Vec<Move> {
+    let mut moves = position.legal_moves();
+    moves.sort_by_key(|m| {
+        let captured = position.get(&m.to).map_or(0, |(_, kind)| value(kind));
+        let promoted = m.promotion.map_or(0, value);
+        -(captured + promoted)
+    });
+    moves
+}
+
+/// Returns the material balance for the side to move.
+fn evaluate(position: &Mailbox) -> i32 {
+    position
+        .pieces()
+        .filter(|(_, _, kind)| *kind != PieceKind::King)
+        .map(|(_, color, kind)| match color == position.side_to_move {
+            true => value(kind),
+            false => -value(kind),
+        })
+        .sum()
+}
+
+/// Returns the value of the kind, as its Piece Type counts it.
+fn value(kind: PieceKind) -> i32 {
+    kind.piece_type().value()
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::book_mod::polyglot::BookEntry;
+    use crate::tablebase_mod::material::Material;
+
+    fn board(fen: &str) -> Board {
+        Board::from_fen(fen).unwrap()
+    }
+
+    #[test]
+    fn test_plays_from_book_first() {
+        let start = board("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let position = Mailbox::from_board(&start);
+        let entries = ["b1a3", "g2g4"]
+            .iter()
+            .zip([1, 10])
+            .map(|(uci, weight)| {
+                BookEntry::new(&position, &position.find_move(uci).unwrap(), weight)
+            })
+            .collect();
+        let engine = Engine::new(1).with_book(Book::from_entries(entries));
+
+        let chosen = engine.choose_move(&start).unwrap();
+        assert_eq!(chosen.chess_move.to_uci(), "g2g4");
+        assert_eq!(chosen.source, MoveSource::Book);
+    }
+
+    #[test]
+    fn test_searches_out_of_book() {
+        let engine = Engine::new(2).with_book(Book::default());
+
+        // The black queen is hanging.
+        let chosen = engine
+            .choose_move(&board("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1"))
+            .unwrap();
+        assert_eq!(chosen.chess_move.to_uci(), "d2d5");
+        assert_eq!(chosen.source, MoveSource::Search);
+    }
+
+    #[test]
+    fn test_finds_mate() {
+        let engine = Engine::default();
+
+        let chosen = engine
+            .choose_move(&board("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1"))
+            .unwrap();
+        assert_eq!(chosen.chess_move.to_uci(), "a1a8");
+    }
+
+    #[test]
+    fn test_plays_from_tablebase() {
+        let mut tablebases = Tablebases::default();
+        tablebases.generate(&"KQvK".parse::<Material>().unwrap());
+        let engine = Engine::new(1).with_tablebases(tablebases);
+
+        let chosen = engine
+            .choose_move(&board("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1"))
+            .unwrap();
+        assert_eq!(chosen.source, MoveSource::Tablebase);
+        assert!(
+            Mailbox::from_board(&board("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1"))
+                .play(&chosen.chess_move)
+                .is_checkmate()
+        );
+    }
+
+    #[test]
+    fn test_no_move_when_game_is_over() {
+        let engine = Engine::default();
+
+        assert!(engine
+            .choose_move(&board("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"))
+            .is_none());
+    }
+}
@@ -1,6 +1,9 @@
+use std::fmt;
+
 /// ChessError is the type of error that can be returned by the program
 /// It has a message that is printed when the error is returned
 /// It also has a print method that prints the error in a special format
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ChessError<'a> {
     pub message: &'a str,
 }
@@ -21,6 +24,68 @@ impl ChessError<'_> {
     pub const FILE_READING: Self = Self {
         message: "Could not read file: file missing or broken",
     };
+    pub const OUT_OF_BOUNDS: Self = Self {
+        message: "Position is outside the 8x8 chess table",
+    };
+    pub const SQUARE_OCCUPIED: Self = Self {
+        message: "Two pieces can not share the same square",
+    };
+    pub const SQUARE_NOTATION: Self = Self {
+        message: "Squares are written as a file (a-h) and a rank (1-8), like e4",
+    };
+    pub const SERIALIZATION: Self = Self {
+        message: "Could not serialize or deserialize the data",
+    };
+    pub const FEN: Self = Self {
+        message: "FEN string is not valid",
+    };
+    pub const CHESS960_INDEX: Self = Self {
+        message: "Chess960 positions are numbered from 0 to 959",
+    };
+    pub const TABLEBASE_MATERIAL: Self = Self {
+        message: "Tablebases are built for up to 4 pieces without pawns, written like KQvK",
+    };
+    pub const TABLEBASE_FILE: Self = Self {
+        message: "Could not read or write the tablebase file",
+    };
+    pub const BOOK_FILE: Self = Self {
+        message: "Could not read the opening book file",
+    };
+    pub const ILLEGAL_MOVE: Self = Self {
+        message: "Move is not legal in this position",
+    };
+    pub const GAME_OVER: Self = Self {
+        message: "The game is already over",
+    };
+    pub const TIME_CONTROL: Self = Self {
+        message:
+            "Time controls are written in seconds, like 300+2 with increment or 300d5 with delay",
+    };
+    pub const NOT_YOUR_TURN: Self = Self {
+        message: "It is not your turn",
+    };
+    pub const UNKNOWN_COMMAND: Self = Self {
+        message: "Unknown command: send MOVE <move> or RESIGN",
+    };
+    pub const HISTORY_PLY: Self = Self {
+        message: "The history has no position at that ply",
+    };
+    pub const PUZZLE: Self = Self {
+        message: "Puzzles are written as a FEN line followed by the number of moves to mate",
+    };
+    pub const ANALYSIS_FORMAT: Self = Self {
+        message:
+            "Boards to analyse are read from a file of FEN lines (fen) or from table files (table)",
+    };
+    pub const RANK_LABEL: Self = Self {
+        message: "Rank labels go from 8 on the first row down to 1 on the last",
+    };
+    pub const FILE_LABEL: Self = Self {
+        message: "File labels (a to h) go on their own line, before or after the rows",
+    };
+    pub const ANALYSIS: Self = Self {
+        message: "Could not analyse the position",
+    };
 
     /// prints the error in special format
     pub fn print(&self) {
@@ -28,6 +93,12 @@ impl ChessError<'_> {
     }
 }
 
+impl fmt::Display for ChessError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +149,176 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_out_of_bounds_error() {
+        let chess_error = ChessError::OUT_OF_BOUNDS;
+        assert_eq!(
+            chess_error.message,
+            "Position is outside the 8x8 chess table"
+        );
+    }
+
+    #[test]
+    fn test_create_square_occupied_error() {
+        let chess_error = ChessError::SQUARE_OCCUPIED;
+        assert_eq!(
+            chess_error.message,
+            "Two pieces can not share the same square"
+        );
+    }
+
+    #[test]
+    fn test_create_square_notation_error() {
+        let chess_error = ChessError::SQUARE_NOTATION;
+        assert_eq!(
+            chess_error.message,
+            "Squares are written as a file (a-h) and a rank (1-8), like e4"
+        );
+    }
+
+    #[test]
+    fn test_create_serialization_error() {
+        let chess_error = ChessError::SERIALIZATION;
+        assert_eq!(
+            chess_error.message,
+            "Could not serialize or deserialize the data"
+        );
+    }
+
+    #[test]
+    fn test_create_fen_error() {
+        let chess_error = ChessError::FEN;
+        assert_eq!(chess_error.message, "FEN string is not valid");
+    }
+
+    #[test]
+    fn test_create_chess960_index_error() {
+        let chess_error = ChessError::CHESS960_INDEX;
+        assert_eq!(
+            chess_error.message,
+            "Chess960 positions are numbered from 0 to 959"
+        );
+    }
+
+    #[test]
+    fn test_create_tablebase_material_error() {
+        let chess_error = ChessError::TABLEBASE_MATERIAL;
+        assert_eq!(
+            chess_error.message,
+            "Tablebases are built for up to 4 pieces without pawns, written like KQvK"
+        );
+    }
+
+    #[test]
+    fn test_create_tablebase_file_error() {
+        let chess_error = ChessError::TABLEBASE_FILE;
+        assert_eq!(
+            chess_error.message,
+            "Could not read or write the tablebase file"
+        );
+    }
+
+    #[test]
+    fn test_create_book_file_error() {
+        let chess_error = ChessError::BOOK_FILE;
+        assert_eq!(chess_error.message, "Could not read the opening book file");
+    }
+
+    #[test]
+    fn test_create_illegal_move_error() {
+        let chess_error = ChessError::ILLEGAL_MOVE;
+        assert_eq!(chess_error.message, "Move is not legal in this position");
+    }
+
+    #[test]
+    fn test_create_game_over_error() {
+        let chess_error = ChessError::GAME_OVER;
+        assert_eq!(chess_error.message, "The game is already over");
+    }
+
+    #[test]
+    fn test_create_time_control_error() {
+        let chess_error = ChessError::TIME_CONTROL;
+        assert_eq!(
+            chess_error.message,
+            "Time controls are written in seconds, like 300+2 with increment or 300d5 with delay"
+        );
+    }
+
+    #[test]
+    fn test_create_not_your_turn_error() {
+        let chess_error = ChessError::NOT_YOUR_TURN;
+        assert_eq!(chess_error.message, "It is not your turn");
+    }
+
+    #[test]
+    fn test_create_unknown_command_error() {
+        let chess_error = ChessError::UNKNOWN_COMMAND;
+        assert_eq!(
+            chess_error.message,
+            "Unknown command: send MOVE <move> or RESIGN"
+        );
+    }
+
+    #[test]
+    fn test_create_history_ply_error() {
+        let chess_error = ChessError::HISTORY_PLY;
+        assert_eq!(
+            chess_error.message,
+            "The history has no position at that ply"
+        );
+    }
+
+    #[test]
+    fn test_create_puzzle_error() {
+        let chess_error = ChessError::PUZZLE;
+        assert_eq!(
+            chess_error.message,
+            "Puzzles are written as a FEN line followed by the number of moves to mate"
+        );
+    }
+
+    #[test]
+    fn test_create_analysis_format_error() {
+        let chess_error = ChessError::ANALYSIS_FORMAT;
+        assert_eq!(
+            chess_error.message,
+            "Boards to analyse are read from a file of FEN lines (fen) or from table files (table)"
+        );
+    }
+
+    #[test]
+    fn test_create_rank_label_error() {
+        let chess_error = ChessError::RANK_LABEL;
+        assert_eq!(
+            chess_error.message,
+            "Rank labels go from 8 on the first row down to 1 on the last"
+        );
+    }
+
+    #[test]
+    fn test_create_file_label_error() {
+        let chess_error = ChessError::FILE_LABEL;
+        assert_eq!(
+            chess_error.message,
+            "File labels (a to h) go on their own line, before or after the rows"
+        );
+    }
+
+    #[test]
+    fn test_create_analysis_error() {
+        let chess_error = ChessError::ANALYSIS;
+        assert_eq!(chess_error.message, "Could not analyse the position");
+    }
+
+    #[test]
+    fn test_display_error() {
+        assert_eq!(
+            ChessError::TABLE_SIZE.to_string(),
+            "Chess table does not respect 8x8 size"
+        );
+    }
+
     #[test]
     fn test_print_error() {
         let chess_error = ChessError::FILE_READING;
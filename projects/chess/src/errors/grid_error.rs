@@ -0,0 +1,44 @@
+use crate::errors::chess_error::ChessError;
+use std::fmt;
+
+/// GridError tells where a table could not be read: the line and the column, both counted from 1,
+/// of the square, label or row at fault, and the ChessError that explains what is wrong with it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GridError {
+    pub line: usize,
+    pub column: usize,
+    pub error: ChessError<'static>,
+}
+
+impl GridError {
+    /// prints the error in special format
+    pub fn print(&self) {
+        println!("ERROR: [{}]", self);
+    }
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.error
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = GridError {
+            line: 3,
+            column: 7,
+            error: ChessError::UKNOWN,
+        };
+
+        assert_eq!(error.to_string(), "line 3, column 7: Unkown piece found");
+    }
+}
@@ -1 +1,3 @@
 pub mod chess_error;
+pub mod grid_error;
+pub mod position_error;
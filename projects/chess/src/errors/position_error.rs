@@ -0,0 +1,83 @@
+use crate::board_mod::castling_rights::CastlingSide;
+use crate::color::Color;
+use crate::position_mod::position::Position;
+use std::fmt;
+
+/// PositionError tells why a position could not happen in a game of chess.
+/// Unlike ChessError, each variant keeps the side or the square that breaks the rules.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PositionError {
+    MissingKing(Color),
+    TooManyKings(Color),
+    /// Pawns can never stand on the first or the last rank.
+    PawnOnBackRank(Position),
+    TooManyPawns(Color),
+    /// The side has more queens, rooks, bishops of a square color or knights than its missing
+    /// pawns could have promoted to.
+    ImpossiblePromotions(Color),
+    /// The castling right has no king on its starting rank or no rook on its file.
+    InvalidCastling(Color, CastlingSide),
+    /// The en passant square was not left behind by a pawn that just moved two squares.
+    InvalidEnPassant(Position),
+    /// The side that just moved left its own king in check.
+    OpponentInCheck(Color),
+}
+
+impl PositionError {
+    /// prints the error in special format
+    pub fn print(&self) {
+        println!("ERROR: [{}]", self);
+    }
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::MissingKing(color) => write!(f, "{:?} has no king", color),
+            PositionError::TooManyKings(color) => write!(f, "{:?} has more than one king", color),
+            PositionError::PawnOnBackRank(square) => {
+                write!(f, "Pawn on {} can not be on the first or last rank", square)
+            }
+            PositionError::TooManyPawns(color) => {
+                write!(f, "{:?} has more than 8 pawns", color)
+            }
+            PositionError::ImpossiblePromotions(color) => write!(
+                f,
+                "{:?} has more pieces than its missing pawns could promote to",
+                color
+            ),
+            PositionError::InvalidCastling(color, side) => write!(
+                f,
+                "{:?} can not castle {:?} side: king or rook are not in place",
+                color, side
+            ),
+            PositionError::InvalidEnPassant(square) => {
+                write!(f, "En passant square {} was not left by a pawn", square)
+            }
+            PositionError::OpponentInCheck(color) => {
+                write!(f, "{:?} is in check but it is not its turn to move", color)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            PositionError::MissingKing(Color::Black).to_string(),
+            "Black has no king"
+        );
+        assert_eq!(
+            PositionError::PawnOnBackRank(Position { x: 4, y: 7 }).to_string(),
+            "Pawn on e8 can not be on the first or last rank"
+        );
+        assert_eq!(
+            PositionError::InvalidCastling(Color::White, CastlingSide::Queen).to_string(),
+            "White can not castle Queen side: king or rook are not in place"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::color::Color;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::position_mod::position::Position;
+
+/// Represents one capture of an exchange: who takes, what is taken and how the material stands after it.
+pub struct ExchangeStep<'a> {
+    pub attacker: &'a Piece,
+    pub captured: &'a Piece,
+    /// Material balance after this capture, seen from the side that started the exchange.
+    pub balance: i32,
+}
+
+/// Represents the Static Exchange Evaluation (SEE) of a square.
+/// Every side captures with its lowest-value attacker first, and each side may stop capturing when
+/// going on would lose material. Only the first `played` steps are worth playing.
+pub struct StaticExchange<'a> {
+    pub target: Position,
+    pub side: Color,
+    pub steps: Vec<ExchangeStep<'a>>,
+    pub played: usize,
+    pub score: i32,
+}
+
+impl<'a> StaticExchange<'a> {
+    /// Evaluates the capture sequence on the target square. Returns None if there is no piece to capture there.
+    pub fn new(pieces: &'a PieceList, target: &Position) -> Option<StaticExchange<'a>> {
+        let (victim_index, victim) = pieces
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.get_position() == target)?;
+
+        let mut removed = vec![false; pieces.len()];
+        removed[victim_index] = true;
+
+        let side = victim.color.opposite();
+        let mut on_square = victim;
+        let mut color_to_capture = side;
+        let mut steps: Vec<ExchangeStep> = Vec::new();
+        let mut gains: Vec<i32> = Vec::new();
+
+        while let Some(i) =
+            Self::least_valuable_attacker(pieces, target, &color_to_capture, &removed)
+        {
+            let attacker = pieces.get(i)?;
+            let captured_value = on_square.get_type().value();
+            let previous_gain = gains.last().copied().unwrap_or(0);
+            let previous_balance = steps.last().map(|s| s.balance).unwrap_or(0);
+
+            gains.push(captured_value - previous_gain);
+            let balance = if color_to_capture == side {
+                previous_balance + captured_value
+            } else {
+                previous_balance - captured_value
+            };
+            steps.push(ExchangeStep {
+                attacker,
+                captured: on_square,
+                balance,
+            });
+
+            removed[i] = true;
+            on_square = attacker;
+            color_to_capture = color_to_capture.opposite();
+        }
+
+        let (played, score) = Self::resolve(&gains);
+
+        Some(StaticExchange {
+            target: *target,
+            side,
+            steps,
+            played,
+            score,
+        })
+    }
+
+    /// Returns the index of the cheapest piece of the given color that can capture on the target,
+    /// skipping removed pieces and attackers whose path is blocked.
+    fn least_valuable_attacker(
+        pieces: &PieceList,
+        target: &Position,
+        color: &Color,
+        removed: &[bool],
+    ) -> Option<usize> {
+        let is_occupied = |square: &Position| {
+            pieces
+                .iter()
+                .enumerate()
+                .any(|(i, p)| !removed[i] && p.get_position() == square)
+        };
+
+        pieces
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| !removed[*i] && &p.color == color)
+            .filter(|(_, p)| p.get_type().can_capture(p, target))
+            .filter(|(_, p)| {
+                p.get_position()
+                    .squares_between(target)
+                    .iter()
+                    .all(|square| !is_occupied(square))
+            })
+            .min_by_key(|(_, p)| p.get_type().value())
+            .map(|(i, _)| i)
+    }
+
+    /// Solves the swap list backwards: each side keeps capturing only while it pays off.
+    /// Returns how many steps are played and the final score for the side that started.
+    fn resolve(gains: &[i32]) -> (usize, i32) {
+        if gains.is_empty() {
+            return (0, 0);
+        }
+
+        let mut resolved = gains.to_vec();
+        for d in (1..resolved.len()).rev() {
+            resolved[d - 1] = -(-resolved[d - 1]).max(resolved[d]);
+        }
+
+        let played = 1
+            + (1..gains.len())
+                .take_while(|&d| resolved[d] > -gains[d - 1])
+                .count();
+
+        (played, resolved[0])
+    }
+
+    /// Formats every step of the exchange and the final score.
+    pub fn explain(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let note = if i < self.played {
+                    ""
+                } else {
+                    " (not worth playing)"
+                };
+                format!(
+                    "{}. {:?} {} from {} takes {:?} {}, balance {:+}{}",
+                    i + 1,
+                    step.attacker.color,
+                    step.attacker.get_type().string(),
+                    step.attacker.get_position(),
+                    step.captured.color,
+                    step.captured.get_type().string(),
+                    step.balance,
+                    note
+                )
+            })
+            .collect();
+
+        lines.push(format!(
+            "Exchange on {}: {:+} for {:?}",
+            self.target, self.score, self.side
+        ));
+        lines
+    }
+
+    /// Prints the explanation of the exchange
+    pub fn print(&self) {
+        for line in self.explain() {
+            println!("{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn piece_list(pieces: &[(&str, i32, i32)]) -> PieceList {
+        let mut piece_list = PieceList::init();
+        for (name, x, y) in pieces {
+            piece_list.push(Piece::new(name, *x, *y).unwrap());
+        }
+        piece_list
+    }
+
+    #[test]
+    fn test_empty_square() {
+        let pieces = piece_list(&[("t", 0, 0), ("P", 3, 3)]);
+
+        assert!(pieces.static_exchange(&Position { x: 5, y: 5 }).is_none());
+    }
+
+    #[test]
+    fn test_no_attackers() {
+        let pieces = piece_list(&[("t", 0, 1), ("P", 3, 3)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(see.steps.len(), 0);
+        assert_eq!(see.score, 0);
+    }
+
+    #[test]
+    fn test_undefended_pawn() {
+        let pieces = piece_list(&[("t", 3, 0), ("P", 3, 3)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(see.side, Color::White);
+        assert_eq!(see.played, 1);
+        assert_eq!(see.score, 1);
+    }
+
+    #[test]
+    fn test_defended_pawn_rook_should_not_capture() {
+        // White rook takes a pawn defended by a black pawn: the rook is lost.
+        let pieces = piece_list(&[("t", 3, 0), ("P", 3, 3), ("P", 2, 4)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(see.steps.len(), 2);
+        assert_eq!(see.steps[1].balance, -4);
+        assert_eq!(see.score, -4);
+    }
+
+    #[test]
+    fn test_lowest_value_attacker_first() {
+        let pieces = piece_list(&[("d", 3, 0), ("p", 2, 2), ("C", 3, 3)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(see.steps[0].attacker.get_type().string(), "Pawn");
+        assert_eq!(see.score, 3);
+    }
+
+    #[test]
+    fn test_defender_stops_when_recapture_loses() {
+        // White pawn takes the knight. Black queen could retake, but the white rook would take the queen.
+        let pieces = piece_list(&[("p", 2, 2), ("t", 3, 0), ("C", 3, 3), ("D", 3, 7)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(see.steps.len(), 3);
+        assert_eq!(see.played, 1);
+        assert_eq!(see.score, 3);
+    }
+
+    #[test]
+    fn test_xray_attacker_behind_rook() {
+        // Two white rooks on the same column: the second one joins once the first has captured.
+        let pieces = piece_list(&[("t", 3, 0), ("t", 3, 1), ("T", 3, 4), ("T", 3, 7)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 4 }).unwrap();
+
+        assert_eq!(see.steps.len(), 3);
+        assert_eq!(see.score, 5);
+    }
+
+    #[test]
+    fn test_blocked_attacker_is_ignored() {
+        let pieces = piece_list(&[("t", 3, 0), ("C", 3, 2), ("P", 3, 4)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 4 }).unwrap();
+
+        assert_eq!(see.steps.len(), 0);
+    }
+
+    #[test]
+    fn test_explain() {
+        let pieces = piece_list(&[("t", 3, 0), ("P", 3, 3), ("P", 2, 4)]);
+        let see = pieces.static_exchange(&Position { x: 3, y: 3 }).unwrap();
+        let lines = see.explain();
+
+        assert_eq!(
+            lines[0],
+            "1. White Rook from d1 takes Black Pawn, balance +1"
+        );
+        assert_eq!(
+            lines[1],
+            "2. Black Pawn from c5 takes White Rook, balance -4"
+        );
+        assert_eq!(lines[2], "Exchange on d4: -4 for White");
+    }
+}
@@ -0,0 +1,256 @@
+use crate::color::Color;
+use crate::game_mod::time_control::TimeControl;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tells the clock how much time has passed. Tests use a FakeTime to move time by hand.
+pub trait TimeSource: Send {
+    /// Returns the time passed since some fixed moment.
+    fn now(&self) -> Duration;
+}
+
+/// Represents the real time, measured from the moment it is created.
+pub struct WallTime {
+    start: Instant,
+}
+
+impl WallTime {
+    pub fn new() -> WallTime {
+        WallTime {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for WallTime {
+    fn default() -> Self {
+        WallTime::new()
+    }
+}
+
+impl TimeSource for WallTime {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Represents a time that only moves when it is told to.
+/// Its clones share the same time, so a test can keep one and give another to the clock.
+#[derive(Debug, Clone, Default)]
+pub struct FakeTime {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl FakeTime {
+    /// Moves the time forward.
+    pub fn advance(&self, time: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += time;
+        }
+    }
+}
+
+impl TimeSource for FakeTime {
+    fn now(&self) -> Duration {
+        self.now.lock().map(|now| *now).unwrap_or_default()
+    }
+}
+
+/// Represents the chess clock of a game: the time left of each player, and whose time is running.
+pub struct GameClock {
+    control: TimeControl,
+    remaining: [Duration; 2],
+    turn: Option<(Color, Duration)>,
+    flagged: Option<Color>,
+    source: Box<dyn TimeSource>,
+}
+
+impl GameClock {
+    /// Creates a stopped clock where both players have the base time of the time control.
+    pub fn new(control: TimeControl, source: Box<dyn TimeSource>) -> GameClock {
+        GameClock {
+            control,
+            remaining: [control.base; 2],
+            turn: None,
+            flagged: None,
+            source,
+        }
+    }
+
+    /// Returns the time control of the clock.
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Starts running the time of the player. The clock must be stopped.
+    pub fn start(&mut self, color: Color) {
+        if self.turn.is_none() && self.flagged.is_none() {
+            self.turn = Some((color, self.source.now()));
+        }
+    }
+
+    /// Returns the player whose time is running, if any.
+    pub fn running(&self) -> Option<Color> {
+        self.turn.map(|(color, _)| color)
+    }
+
+    /// Returns the time the player has left right now.
+    pub fn remaining(&self, color: Color) -> Duration {
+        let left = self.remaining[slot(color)];
+        match self.turn {
+            Some((running, _)) if running == color => left.saturating_sub(self.spent()),
+            _ => left,
+        }
+    }
+
+    /// Returns the player who ran out of time, if any.
+    pub fn flagged(&self) -> Option<Color> {
+        self.flagged.or_else(|| {
+            let (color, _) = self.turn?;
+            self.remaining(color).is_zero().then_some(color)
+        })
+    }
+
+    /// Ends the turn of the running player after a move: its time is charged, the increment added
+    /// and the time of the rival starts running.
+    /// Returns the time the player is left with, or None if it ran out of time first.
+    pub fn end_turn(&mut self) -> Option<Duration> {
+        let color = self.stop()?;
+        let left = &mut self.remaining[slot(color)];
+        *left += self.control.increment;
+        let left = *left;
+        self.start(color.opposite());
+        Some(left)
+    }
+
+    /// Stops the clock, charging the running player for the time it used.
+    /// Returns that player, or None if the clock was stopped or the player ran out of time.
+    pub fn stop(&mut self) -> Option<Color> {
+        if let Some(color) = self.flagged() {
+            self.remaining[slot(color)] = Duration::ZERO;
+            self.flagged = Some(color);
+            self.turn = None;
+            return None;
+        }
+        let (color, _) = self.turn?;
+        self.remaining[slot(color)] = self.remaining(color);
+        self.turn = None;
+        Some(color)
+    }
+
+    /// Returns the time the running player has used in this turn, the delay left out.
+    fn spent(&self) -> Duration {
+        match self.turn {
+            Some((_, started)) => self
+                .source
+                .now()
+                .saturating_sub(started)
+                .saturating_sub(self.control.delay),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+impl fmt::Display for GameClock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "White {} - Black {}",
+            format_clock(self.remaining(Color::White)),
+            format_clock(self.remaining(Color::Black))
+        )
+    }
+}
+
+/// Returns the time written as hours, minutes and seconds, like "0:04:58".
+pub fn format_clock(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn slot(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn clock(control: &str) -> (GameClock, FakeTime) {
+        let time = FakeTime::default();
+        let clock = GameClock::new(control.parse().unwrap(), Box::new(time.clone()));
+        (clock, time)
+    }
+
+    #[test]
+    fn test_only_running_side_uses_time() {
+        let (mut clock, time) = clock("300");
+        clock.start(Color::White);
+        time.advance(seconds(10));
+
+        assert_eq!(clock.remaining(Color::White), seconds(290));
+        assert_eq!(clock.remaining(Color::Black), seconds(300));
+        assert_eq!(clock.running(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_increment() {
+        let (mut clock, time) = clock("300+2");
+        clock.start(Color::White);
+        time.advance(seconds(10));
+
+        assert_eq!(clock.end_turn(), Some(seconds(292)));
+        assert_eq!(clock.running(), Some(Color::Black));
+        time.advance(seconds(1));
+        assert_eq!(clock.remaining(Color::Black), seconds(299));
+        assert_eq!(clock.remaining(Color::White), seconds(292));
+    }
+
+    #[test]
+    fn test_delay() {
+        let (mut clock, time) = clock("60d5");
+        clock.start(Color::White);
+        time.advance(seconds(4));
+        assert_eq!(clock.remaining(Color::White), seconds(60));
+
+        time.advance(seconds(3));
+        assert_eq!(clock.end_turn(), Some(seconds(58)));
+    }
+
+    #[test]
+    fn test_flag_fall() {
+        let (mut clock, time) = clock("60+5");
+        clock.start(Color::White);
+        time.advance(seconds(59));
+        assert_eq!(clock.flagged(), None);
+
+        time.advance(seconds(1));
+        assert_eq!(clock.flagged(), Some(Color::White));
+        assert_eq!(clock.end_turn(), None);
+        assert_eq!(clock.running(), None);
+        assert_eq!(clock.remaining(Color::White), Duration::ZERO);
+        assert_eq!(clock.flagged(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_display() {
+        let (mut clock, time) = clock("3600");
+        clock.start(Color::Black);
+        time.advance(Duration::from_millis(62_500));
+
+        assert_eq!(clock.to_string(), "White 1:00:00 - Black 0:58:57");
+    }
+}
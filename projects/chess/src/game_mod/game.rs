@@ -0,0 +1,302 @@
+use crate::board_mod::board::Board;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::game_mod::clock::GameClock;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use std::fmt;
+use std::time::Duration;
+
+/// Tells how a game ended.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    /// A player ran out of time. If the rival only has its king left, the game is a draw.
+    TimeForfeit(Color),
+    Resignation(Color),
+}
+
+/// Represents the end of a game: who won, if anyone, and how.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GameResult {
+    pub winner: Option<Color>,
+    pub termination: Termination,
+}
+
+impl GameResult {
+    /// Returns the result as PGN writes it: "1-0", "0-1" or "1/2-1/2".
+    pub fn score(&self) -> &'static str {
+        match self.winner {
+            Some(Color::White) => "1-0",
+            Some(Color::Black) => "0-1",
+            None => "1/2-1/2",
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.termination, self.winner) {
+            (Termination::Checkmate, Some(winner)) => write!(f, "{:?} wins by checkmate", winner),
+            (Termination::Resignation(loser), _) => write!(f, "{:?} resigns", loser),
+            (Termination::TimeForfeit(loser), Some(_)) => write!(f, "{:?} lost on time", loser),
+            (Termination::TimeForfeit(loser), None) => write!(
+                f,
+                "{:?} ran out of time, but {:?} can not mate: draw",
+                loser,
+                loser.opposite()
+            ),
+            _ => write!(f, "Draw by stalemate"),
+        }
+    }
+}
+
+/// Represents a move of the game, with its notation and the time its player had left after it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameMove {
+    pub chess_move: Move,
+    pub san: String,
+    pub clock: Option<Duration>,
+}
+
+/// Represents a game being played from a starting Board, optionally with a clock.
+pub struct Game {
+    start: Board,
+    position: Mailbox,
+    moves: Vec<GameMove>,
+    clock: Option<GameClock>,
+    result: Option<GameResult>,
+}
+
+impl Game {
+    /// Creates a game that starts from the Board, without a clock.
+    pub fn new(board: Board) -> Game {
+        let position = Mailbox::from_board(&board);
+        let mut game = Game {
+            start: board,
+            position,
+            moves: Vec::new(),
+            clock: None,
+            result: None,
+        };
+        game.check_position();
+        game
+    }
+
+    /// Adds a clock to the game. The time of the side to move starts running.
+    pub fn with_clock(mut self, mut clock: GameClock) -> Game {
+        if self.result.is_none() {
+            clock.start(self.position.side_to_move);
+        }
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Returns the Board the game started from.
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> &Mailbox {
+        &self.position
+    }
+
+    /// Returns the moves played so far.
+    pub fn moves(&self) -> &[GameMove] {
+        &self.moves
+    }
+
+    /// Returns the clock of the game, if it has one.
+    pub fn clock(&self) -> Option<&GameClock> {
+        self.clock.as_ref()
+    }
+
+    /// Returns the result, once the game is over.
+    pub fn result(&self) -> Option<&GameResult> {
+        self.result.as_ref()
+    }
+
+    /// Plays a move of the side to move, written in UCI ("g1f3") or SAN ("Nf3").
+    /// It fails if the move is not legal or the game is over, which includes running out of time.
+    pub fn play(&mut self, text: &str) -> Result<&GameMove, ChessError<'static>> {
+        if self.check_flag().is_some() {
+            return Err(ChessError::GAME_OVER);
+        }
+        let chess_move = self
+            .position
+            .find_move(text)
+            .or_else(|| self.position.find_san(text))
+            .ok_or(ChessError::ILLEGAL_MOVE)?;
+
+        let clock = match self.clock.as_mut().map(|clock| clock.end_turn()) {
+            Some(None) => {
+                self.check_flag();
+                return Err(ChessError::GAME_OVER);
+            }
+            Some(left) => left,
+            None => None,
+        };
+        self.moves.push(GameMove {
+            chess_move,
+            san: self.position.to_san(&chess_move),
+            clock,
+        });
+        self.position = self.position.play(&chess_move);
+        self.check_position();
+        self.moves.last().ok_or(ChessError::ILLEGAL_MOVE)
+    }
+
+    /// Ends the game if the running player is out of time, and returns the result if the game is over.
+    pub fn check_flag(&mut self) -> Option<&GameResult> {
+        if self.result.is_none() {
+            let flagged = self.clock.as_ref().and_then(|clock| clock.flagged());
+            if let Some(loser) = flagged {
+                let winner = loser.opposite();
+                self.finish(GameResult {
+                    winner: self.can_mate(winner).then_some(winner),
+                    termination: Termination::TimeForfeit(loser),
+                });
+            }
+        }
+        self.result.as_ref()
+    }
+
+    /// Ends the game with the player resigning.
+    pub fn resign(&mut self, color: Color) {
+        if self.check_flag().is_none() {
+            self.finish(GameResult {
+                winner: Some(color.opposite()),
+                termination: Termination::Resignation(color),
+            });
+        }
+    }
+
+    /// Ends the game if the side to move is mated or stalemated.
+    fn check_position(&mut self) {
+        let termination = if self.position.is_checkmate() {
+            Termination::Checkmate
+        } else if self.position.is_stalemate() {
+            Termination::Stalemate
+        } else {
+            return;
+        };
+        let winner = match termination {
+            Termination::Checkmate => Some(self.position.side_to_move.opposite()),
+            _ => None,
+        };
+        self.finish(GameResult {
+            winner,
+            termination,
+        });
+    }
+
+    fn finish(&mut self, result: GameResult) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.stop();
+        }
+        self.result = Some(result);
+    }
+
+    /// Returns false if the player only has its king, so it can never give mate.
+    fn can_mate(&self, color: Color) -> bool {
+        self.position
+            .pieces()
+            .any(|(_, c, kind)| c == color && kind != PieceKind::King)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_mod::clock::FakeTime;
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    fn game(fen: &str) -> Game {
+        Game::new(Board::from_fen(fen).unwrap())
+    }
+
+    fn timed_game(fen: &str, control: &str) -> (Game, FakeTime) {
+        let time = FakeTime::default();
+        let clock = GameClock::new(control.parse().unwrap(), Box::new(time.clone()));
+        (game(fen).with_clock(clock), time)
+    }
+
+    #[test]
+    fn test_play_uci_and_san() {
+        let mut game = game(START);
+
+        assert_eq!(game.play("e2e4").unwrap().san, "e4");
+        assert_eq!(game.play("Nc6").unwrap().san, "Nc6");
+        assert_eq!(game.play("e4e6"), Err(ChessError::ILLEGAL_MOVE));
+        assert_eq!(game.moves().len(), 2);
+        assert_eq!(game.position().side_to_move, Color::White);
+    }
+
+    #[test]
+    fn test_checkmate_ends_the_game() {
+        let mut game = game(START);
+        for text in ["f3", "e5", "g4", "Qh4#"] {
+            game.play(text).unwrap();
+        }
+
+        let result = game.result().unwrap();
+        assert_eq!(result.winner, Some(Color::Black));
+        assert_eq!(result.termination, Termination::Checkmate);
+        assert_eq!(game.play("a3"), Err(ChessError::GAME_OVER));
+    }
+
+    #[test]
+    fn test_clock_is_saved_with_each_move() {
+        let (mut game, time) = timed_game(START, "300+2");
+        time.advance(Duration::from_secs(5));
+        game.play("e4").unwrap();
+        time.advance(Duration::from_secs(12));
+        game.play("e5").unwrap();
+
+        let clocks: Vec<Option<Duration>> = game.moves().iter().map(|m| m.clock).collect();
+        assert_eq!(
+            clocks,
+            vec![
+                Some(Duration::from_secs(297)),
+                Some(Duration::from_secs(290))
+            ]
+        );
+        assert_eq!(game.clock().unwrap().running(), Some(Color::White));
+    }
+
+    #[test]
+    fn test_flag_fall() {
+        let (mut game, time) = timed_game(START, "60");
+        game.play("e4").unwrap();
+        time.advance(Duration::from_secs(61));
+
+        assert_eq!(game.play("e5"), Err(ChessError::GAME_OVER));
+        let result = game.result().unwrap();
+        assert_eq!(result.winner, Some(Color::White));
+        assert_eq!(result.termination, Termination::TimeForfeit(Color::Black));
+        assert_eq!(game.moves().len(), 1);
+    }
+
+    #[test]
+    fn test_flag_fall_against_lone_king_is_a_draw() {
+        let (mut game, time) = timed_game("4k3/8/8/8/8/8/8/3QK3 w - - 0 1", "10");
+        time.advance(Duration::from_secs(10));
+
+        let result = game.check_flag().unwrap();
+        assert_eq!(result.winner, None);
+        assert_eq!(result.score(), "1/2-1/2");
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = game(START);
+        game.resign(Color::White);
+
+        assert_eq!(game.result().unwrap().score(), "0-1");
+        assert_eq!(game.result().unwrap().to_string(), "White resigns");
+    }
+}
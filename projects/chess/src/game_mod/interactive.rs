@@ -0,0 +1,93 @@
+use crate::game_mod::game::Game;
+use std::io::{self, BufRead, Write};
+
+/// Plays the game reading one move per line from the input, in UCI or SAN, until it is over or the
+/// input ends. Before every move it writes the table, the clocks and whose turn it is.
+/// The player to move can also write "resign".
+pub fn play_interactive<R: BufRead, W: Write>(
+    game: &mut Game,
+    input: R,
+    output: &mut W,
+) -> io::Result<()> {
+    let mut lines = input.lines();
+    while game.result().is_none() {
+        let position = game.position();
+        writeln!(output, "{}", position.to_board().to_table())?;
+        if let Some(clock) = game.clock() {
+            writeln!(output, "{}", clock)?;
+        }
+        write!(output, "{:?} to move: ", position.side_to_move)?;
+        output.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        if game.check_flag().is_some() {
+            break;
+        }
+        match line.trim() {
+            "" => {}
+            "resign" => game.resign(game.position().side_to_move),
+            text => {
+                if let Err(e) = game.play(text) {
+                    writeln!(output, "ERROR: [{}]", e)?;
+                }
+            }
+        }
+    }
+
+    writeln!(output)?;
+    match game.result() {
+        Some(result) => writeln!(output, "{} ({})", result, result.score()),
+        None => writeln!(output, "Game stopped"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+    use crate::game_mod::clock::{FakeTime, GameClock};
+    use std::time::Duration;
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    fn run(game: &mut Game, input: &str) -> String {
+        let mut output = Vec::new();
+        play_interactive(game, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_plays_until_mate() {
+        let mut game = Game::new(Board::from_fen(START).unwrap());
+        let output = run(&mut game, "f3\ne7e5\nxx\ng4\nQh4#\n");
+
+        assert!(output.contains("White to move: "));
+        assert!(output.contains("ERROR: [Move is not legal in this position]"));
+        assert!(output.ends_with("Black wins by checkmate (0-1)\n"));
+        assert_eq!(game.moves().len(), 4);
+    }
+
+    #[test]
+    fn test_shows_clocks_and_detects_flag_fall() {
+        let time = FakeTime::default();
+        let clock = GameClock::new("60".parse().unwrap(), Box::new(time.clone()));
+        let mut game = Game::new(Board::from_fen(START).unwrap()).with_clock(clock);
+        time.advance(Duration::from_secs(61));
+
+        let output = run(&mut game, "e4\n");
+        assert!(output.contains("White 0:00:00 - Black 0:01:00\n"));
+        assert!(output.ends_with("White lost on time (0-1)\n"));
+        assert!(game.moves().is_empty());
+    }
+
+    #[test]
+    fn test_resign_and_end_of_input() {
+        let mut game = Game::new(Board::from_fen(START).unwrap());
+        assert!(run(&mut game, "e4\n").ends_with("Game stopped\n"));
+
+        assert!(run(&mut game, "resign\n").ends_with("Black resigns (1-0)\n"));
+    }
+}
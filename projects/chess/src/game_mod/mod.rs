@@ -0,0 +1,5 @@
+pub mod clock;
+pub mod game;
+pub mod interactive;
+pub mod pgn;
+pub mod time_control;
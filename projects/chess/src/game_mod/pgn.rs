@@ -0,0 +1,172 @@
+use crate::color::Color;
+use crate::game_mod::clock::format_clock;
+use crate::game_mod::game::{Game, Termination};
+
+/// FEN of the standard starting position. Games from any other position write it in their tags.
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// PGN lines of moves are kept under this length.
+const LINE_LENGTH: usize = 79;
+
+impl Game {
+    /// Returns the game in PGN. When the game has a clock, the time control is written in the
+    /// TimeControl tag and the time left after each move in a `[%clk]` comment.
+    pub fn to_pgn(&self) -> String {
+        let score = self.result().map_or("*", |r| r.score());
+        let mut tags = vec![
+            ("Event", "Casual game".to_string()),
+            ("Site", "?".to_string()),
+            ("Date", "????.??.??".to_string()),
+            ("Round", "-".to_string()),
+            ("White", "White".to_string()),
+            ("Black", "Black".to_string()),
+            ("Result", score.to_string()),
+        ];
+        let fen = self.start().to_fen();
+        if fen != START_FEN {
+            tags.push(("SetUp", "1".to_string()));
+            tags.push(("FEN", fen));
+        }
+        if let Some(clock) = self.clock() {
+            tags.push(("TimeControl", clock.control().to_string()));
+        }
+        if let Some(result) = self.result() {
+            let termination = match result.termination {
+                Termination::TimeForfeit(_) => "time forfeit",
+                _ => "normal",
+            };
+            tags.push(("Termination", termination.to_string()));
+        }
+
+        let mut pgn: String = tags
+            .iter()
+            .map(|(name, value)| format!("[{} \"{}\"]\n", name, value))
+            .collect();
+        pgn.push('\n');
+        pgn.push_str(&wrap(&self.movetext(score)));
+        pgn.push('\n');
+        pgn
+    }
+
+    /// Returns the moves with their numbers and clock comments, followed by the result.
+    fn movetext(&self, score: &str) -> Vec<String> {
+        let start = self.start();
+        let mut number = start.fullmove_number();
+        let mut color = start.side_to_move();
+        let mut tokens = Vec::new();
+        if color == Color::Black {
+            tokens.push(format!("{}...", number));
+        }
+
+        for game_move in self.moves() {
+            if color == Color::White {
+                tokens.push(format!("{}.", number));
+            }
+            tokens.push(game_move.san.clone());
+            if let Some(left) = game_move.clock {
+                tokens.push(format!("{{[%clk {}]}}", format_clock(left)));
+            }
+            if color == Color::Black {
+                number += 1;
+            }
+            color = color.opposite();
+        }
+        tokens.push(score.to_string());
+        tokens
+    }
+}
+
+/// Joins the tokens with spaces into lines no longer than LINE_LENGTH.
+fn wrap(tokens: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + token.len() + 1 > LINE_LENGTH {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(token);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board_mod::board::Board;
+    use crate::game_mod::clock::{FakeTime, GameClock};
+    use crate::game_mod::game::Game;
+    use std::time::Duration;
+
+    fn start() -> Board {
+        Board::from_fen(super::START_FEN).unwrap()
+    }
+
+    #[test]
+    fn test_pgn_without_clock() {
+        let mut game = Game::new(start());
+        for text in ["f3", "e5", "g4", "Qh4#"] {
+            game.play(text).unwrap();
+        }
+
+        assert_eq!(
+            game.to_pgn(),
+            "[Event \"Casual game\"]\n\
+             [Site \"?\"]\n\
+             [Date \"????.??.??\"]\n\
+             [Round \"-\"]\n\
+             [White \"White\"]\n\
+             [Black \"Black\"]\n\
+             [Result \"0-1\"]\n\
+             [Termination \"normal\"]\n\
+             \n\
+             1. f3 e5 2. g4 Qh4# 0-1\n"
+        );
+    }
+
+    #[test]
+    fn test_pgn_with_clock() {
+        let time = FakeTime::default();
+        let clock = GameClock::new("180+2".parse().unwrap(), Box::new(time.clone()));
+        let mut game = Game::new(start()).with_clock(clock);
+        game.play("e4").unwrap();
+        time.advance(Duration::from_secs(30));
+        game.play("c5").unwrap();
+        time.advance(Duration::from_secs(200));
+        game.check_flag();
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[TimeControl \"180+2\"]\n"));
+        assert!(pgn.contains("[Termination \"time forfeit\"]\n"));
+        assert!(pgn.ends_with("1. e4 {[%clk 0:03:02]} c5 {[%clk 0:02:32]} 0-1\n"));
+    }
+
+    #[test]
+    fn test_pgn_from_position_with_black_to_move() {
+        let board = Board::from_fen("8/8/8/8/8/1k6/2q5/K7 b - - 0 40").unwrap();
+        let mut game = Game::new(board);
+        game.play("Qc1#").unwrap();
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"8/8/8/8/8/1k6/2q5/K7 b - - 0 40\"]\n"));
+        assert!(pgn.ends_with("\n40... Qc1# 0-1\n"));
+    }
+
+    #[test]
+    fn test_long_games_are_wrapped() {
+        let mut game = Game::new(start());
+        for _ in 0..10 {
+            for text in ["Nf3", "Nf6", "Ng1", "Ng8"] {
+                game.play(text).unwrap();
+            }
+        }
+
+        let pgn = game.to_pgn();
+        let movetext = pgn.split("\n\n").nth(1).unwrap();
+        assert!(movetext.lines().count() > 1);
+        assert!(movetext.lines().all(|line| line.len() <= 79));
+        assert!(movetext.ends_with("20. Ng1 Ng8 *\n"));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::errors::chess_error::ChessError;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Represents the time each player gets for the game: a base time, plus an increment added after
+/// every move, and a delay that runs before the clock starts counting on every move.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+    pub delay: Duration,
+}
+
+impl TimeControl {
+    /// Creates a time control where each move adds `increment` to the clock of the player who made it.
+    pub fn increment(base: Duration, increment: Duration) -> TimeControl {
+        TimeControl {
+            base,
+            increment,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Creates a time control where the clock waits `delay` before it starts counting on every move.
+    pub fn delay(base: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            base,
+            increment: Duration::ZERO,
+            delay,
+        }
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = ChessError<'static>;
+
+    /// Parses a time control written in seconds, as the PGN TimeControl tag does: "300" has no
+    /// increment, "300+2" adds 2 seconds per move and "300d5" waits 5 seconds on every move.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let seconds = |s: &str| -> Result<Duration, ChessError<'static>> {
+            s.parse()
+                .map(Duration::from_secs)
+                .map_err(|_| ChessError::TIME_CONTROL)
+        };
+        let (rest, delay) = match text.trim().split_once('d') {
+            Some((rest, delay)) => (rest, seconds(delay)?),
+            None => (text.trim(), Duration::ZERO),
+        };
+        let (base, increment) = match rest.split_once('+') {
+            Some((base, increment)) => (seconds(base)?, seconds(increment)?),
+            None => (seconds(rest)?, Duration::ZERO),
+        };
+        if base.is_zero() {
+            return Err(ChessError::TIME_CONTROL);
+        }
+        Ok(TimeControl {
+            base,
+            increment,
+            delay,
+        })
+    }
+}
+
+impl fmt::Display for TimeControl {
+    /// Writes the time control in the format `from_str` reads.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.base.as_secs())?;
+        if !self.increment.is_zero() {
+            write!(f, "+{}", self.increment.as_secs())?;
+        }
+        if !self.delay.is_zero() {
+            write!(f, "d{}", self.delay.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "300+2".parse(),
+            Ok(TimeControl::increment(
+                Duration::from_secs(300),
+                Duration::from_secs(2)
+            ))
+        );
+        assert_eq!(
+            "60d5".parse(),
+            Ok(TimeControl::delay(
+                Duration::from_secs(60),
+                Duration::from_secs(5)
+            ))
+        );
+        assert_eq!(
+            "900".parse(),
+            Ok(TimeControl::increment(
+                Duration::from_secs(900),
+                Duration::ZERO
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_time_controls() {
+        for text in ["", "0+2", "5m", "300+", "+2", "300d"] {
+            assert_eq!(text.parse::<TimeControl>(), Err(ChessError::TIME_CONTROL));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        for text in ["300+2", "60d5", "900", "180+2d3"] {
+            assert_eq!(text.parse::<TimeControl>().unwrap().to_string(), text);
+        }
+    }
+}
@@ -1,9 +1,22 @@
+pub mod analysis_mod;
+pub mod board_mod;
+pub mod book_mod;
+pub mod chess960_mod;
 pub mod color;
+pub mod engine_mod;
 pub mod errors;
+pub mod exchange_mod;
+pub mod game_mod;
+pub mod move_gen_mod;
+pub mod network_mod;
 pub mod piece_list_mod;
 pub mod piece_mod;
 pub mod piece_movement;
 pub mod piece_type_mod;
 pub mod position_mod;
+pub mod puzzle_mod;
 pub mod results;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod tablebase_mod;
 pub mod utils;
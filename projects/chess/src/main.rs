@@ -1,34 +1,234 @@
+use chess::analysis_mod::batch::{analyse_all, read_fen_lines, read_table_files, BoardInput};
+use chess::board_mod::board::Board;
+use chess::book_mod::polyglot::Book;
+use chess::chess960_mod::chess960::chess960_board;
+use chess::engine_mod::engine::Engine;
 use chess::errors::chess_error::ChessError;
-use chess::utils::parsing::parse_config;
-use chess::utils::parsing::parse_pieces;
+use chess::game_mod::clock::{GameClock, WallTime};
+use chess::game_mod::game::Game;
+use chess::game_mod::interactive::play_interactive;
+use chess::game_mod::time_control::TimeControl;
+use chess::move_gen_mod::mailbox::Mailbox;
+use chess::puzzle_mod::mate_solver::{format_tree, Puzzle};
+use chess::tablebase_mod::material::Material;
+use chess::tablebase_mod::tablebase::{Tablebase, Tablebases};
+use chess::utils::parsing::{parse_config, parse_grid};
 use std::env;
 use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 /// Recieves a file to be read.
-/// Then, it parses it to obtain a PieceList. Pieces have chess piece's behavior.
+/// Then, it parses it to obtain a Board. Pieces have chess piece's behavior.
 /// It prints the result of the fight between the pieces.
+///
+/// Other commands:
+/// - `chess960 <index>` prints the Chess960 starting position with that index (0 to 959)
+///   in the table format and in FEN.
+/// - `tablebase <material> <file>` builds the tablebase of an ending, like KQvK, into the file.
+/// - `probe <file> <fen>` prints the outcome and the best move of a position from a tablebase file.
+/// - `book <file> [fen]` lists the moves of a Polyglot opening book for the position (the starting
+///   position by default) with their weights, and the move the engine plays.
+/// - `play [time control] [fen]` plays a game reading moves from the standard input, with clocks
+///   when a time control like 300+2 (increment) or 300d5 (delay) is given, and prints its PGN.
+/// - `validate <fen>` tells whether the position could happen in a game, or why not.
+/// - `mate <file>` proves or refutes the forced mate of a puzzle file (a FEN line and the number
+///   of moves), printing the main line and every defense as a tree.
+/// - `analyse fen <file>` and `analyse table <file>...` analyse many boards on every core, read
+///   from a file with a FEN per line or from table files, printing the results in input order.
 fn main() {
     let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("chess960") if args.len() > 2 => return print_chess960(&args[2]),
+        Some("tablebase") if args.len() > 3 => return build_tablebase(&args[2], &args[3]),
+        Some("probe") if args.len() > 3 => return probe_tablebase(&args[2], &args[3]),
+        Some("book") if args.len() > 2 => return list_book_moves(&args[2], args.get(3)),
+        Some("play") => return play_game(args.get(2), args.get(3)),
+        Some("validate") if args.len() > 2 => return validate_position(&args[2]),
+        Some("mate") if args.len() > 2 => return solve_puzzle(&args[2]),
+        Some("analyse") if args.len() > 3 => return analyse_boards(&args[2], &args[3..]),
+        _ => {}
+    }
+
     let file_path = parse_config(&args);
 
     let contents = fs::read_to_string(file_path);
 
     match contents {
-        Ok(c) => {
-            let pieces = parse_pieces(&c);
-            if let Some(c) = pieces {
-                let result = c.fight();
-                result.print();
+        Ok(c) => match parse_grid(&c) {
+            Ok(pieces) => match Board::from_pieces(pieces) {
+                Ok(board) => match board.fight() {
+                    Ok(result) => result.print(),
+                    Err(e) => e.print(),
+                },
+                Err(e) => e.print(),
+            },
+            Err(e) => e.print(),
+        },
+        Err(_e) => ChessError::FILE_READING.print(),
+    }
+}
+
+/// Prints the Chess960 starting position with the given index, or the error if it is not valid.
+fn print_chess960(index: &str) {
+    let board = index
+        .parse()
+        .map_err(|_| ChessError::CHESS960_INDEX)
+        .and_then(chess960_board);
+
+    match board {
+        Ok(board) => {
+            println!("{}", board.to_table());
+            println!();
+            println!("{}", board.to_fen());
+        }
+        Err(e) => e.print(),
+    }
+}
+
+/// Builds the tablebase of the material and writes it to the file.
+fn build_tablebase(material: &str, path: &str) {
+    let material: Material = match material.parse() {
+        Ok(material) => material,
+        Err(e) => return e.print(),
+    };
+
+    let mut tablebases = Tablebases::default();
+    tablebases.generate(&material);
+    let saved = tablebases
+        .get(&material)
+        .ok_or(ChessError::TABLEBASE_MATERIAL)
+        .and_then(|table| table.save(Path::new(path)));
+
+    match saved {
+        Ok(_) => println!("{} written to {}", material, path),
+        Err(e) => e.print(),
+    }
+}
+
+/// Prints the outcome of the position and its best move, as found in the tablebase file.
+fn probe_tablebase(path: &str, fen: &str) {
+    let table = match Tablebase::load(Path::new(path)) {
+        Ok(table) => table,
+        Err(e) => return e.print(),
+    };
+    let board = match Board::from_fen(fen) {
+        Ok(board) => board,
+        Err(e) => return e.print(),
+    };
+
+    let mut tablebases = Tablebases::default();
+    tablebases.insert(table);
+    match tablebases.probe(&board) {
+        Some(probe) => println!("{}", probe),
+        None => return ChessError::TABLEBASE_MATERIAL.print(),
+    }
+    if let Some(best) = tablebases.best_move(&board) {
+        println!("Best move: {}", best);
+    }
+}
+
+/// Prints the moves of the opening book for the position with their weights, and the engine's choice.
+fn list_book_moves(path: &str, fen: Option<&String>) {
+    let book = match Book::load(Path::new(path)) {
+        Ok(book) => book,
+        Err(e) => return e.print(),
+    };
+    let board = match fen {
+        Some(fen) => Board::from_fen(fen),
+        None => Board::from_fen(START_FEN),
+    };
+    let board = match board {
+        Ok(board) => board,
+        Err(e) => return e.print(),
+    };
+
+    let moves = book.moves(&Mailbox::from_board(&board));
+    if moves.is_empty() {
+        println!("The position is not in the book");
+    }
+    for book_move in moves {
+        println!("{} {}", book_move.chess_move, book_move.weight);
+    }
+    if let Some(chosen) = Engine::default().with_book(book).choose_move(&board) {
+        println!("Engine plays: {} ({:?})", chosen.chess_move, chosen.source);
+    }
+}
+
+/// Plays a game in the terminal, timed if a time control is given, and prints its PGN at the end.
+fn play_game(control: Option<&String>, fen: Option<&String>) {
+    let board = match Board::from_fen(fen.map_or(START_FEN, |f| f.as_str())) {
+        Ok(board) => board,
+        Err(e) => return e.print(),
+    };
+    let mut game = Game::new(board);
+    if let Some(control) = control.filter(|c| c.as_str() != "-") {
+        match control.parse::<TimeControl>() {
+            Ok(control) => {
+                game = game.with_clock(GameClock::new(control, Box::new(WallTime::new())))
             }
+            Err(e) => return e.print(),
         }
-        Err(_e) => ChessError::FILE_READING.print(),
     }
+
+    let mut output = io::stdout();
+    if play_interactive(&mut game, io::stdin().lock(), &mut output).is_err() {
+        return ChessError::FILE_READING.print();
+    }
+    println!();
+    print!("{}", game.to_pgn());
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chess::color::Color;
-    use chess::piece_list_mod::piece_list::PieceList;
-    use chess::piece_mod::piece::Piece;
+/// Prints whether the position could happen in a game, or the reason it can not.
+fn validate_position(fen: &str) {
+    match Board::from_fen(fen).map(|board| board.validate()) {
+        Ok(Ok(())) => println!("The position is valid"),
+        Ok(Err(e)) => e.print(),
+        Err(e) => e.print(),
+    }
+}
+
+/// Prints the fastest forced mate of the puzzle as a tree, or that there is none.
+fn solve_puzzle(path: &str) {
+    let puzzle = match fs::read_to_string(path) {
+        Ok(text) => Puzzle::parse(&text),
+        Err(_e) => Err(ChessError::FILE_READING),
+    };
+    let puzzle = match puzzle {
+        Ok(puzzle) => puzzle,
+        Err(e) => return e.print(),
+    };
+
+    match puzzle.solve() {
+        Some(line) => {
+            println!("Mate in {}: {}", line.length(), line.main_line().join(" "));
+            let position = Mailbox::from_board(&puzzle.board);
+            for row in format_tree(&position, &line) {
+                println!("{}", row);
+            }
+        }
+        None => println!("No forced mate in {}", puzzle.moves),
+    }
+}
+
+/// Analyses the boards on every core, printing the results in order and the progress to stderr.
+fn analyse_boards(format: &str, paths: &[String]) {
+    let inputs: Vec<BoardInput> = match format {
+        "fen" => match fs::read_to_string(&paths[0]) {
+            Ok(text) => read_fen_lines(&text),
+            Err(_e) => return ChessError::FILE_READING.print(),
+        },
+        "table" => read_table_files(paths),
+        _ => return ChessError::ANALYSIS_FORMAT.print(),
+    };
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    analyse_all(inputs, threads, |report, progress| {
+        println!("{}", report);
+        eprint!("\rAnalysed {}", progress);
+    });
+    eprintln!();
 }
@@ -0,0 +1,67 @@
+use crate::board_mod::castling_rights::CastlingSide;
+use crate::color::Color;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+use std::fmt;
+
+/// Represents a move: the piece on `from` goes to `to`.
+/// For castling, `from` and `to` are the squares of the king before and after castling, and `castling`
+/// tells the side, so the move is also clear in Chess960 where the king may not move at all.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+    pub promotion: Option<PieceKind>,
+    pub castling: Option<CastlingSide>,
+}
+
+impl Move {
+    /// Creates a move that is neither a promotion nor a castling.
+    pub fn new(from: Position, to: Position) -> Move {
+        Move {
+            from,
+            to,
+            promotion: None,
+            castling: None,
+        }
+    }
+
+    /// Returns the move in UCI notation, like "e2e4" or "e7e8q".
+    pub fn to_uci(&self) -> String {
+        let mut uci = format!("{}{}", self.from, self.to);
+        if let Some(kind) = self.promotion {
+            uci.push(kind.fen_char(Color::Black));
+        }
+        uci
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_uci() {
+        let e2 = Position::from_algebraic("e2").unwrap();
+        let e4 = Position::from_algebraic("e4").unwrap();
+
+        assert_eq!(Move::new(e2, e4).to_uci(), "e2e4");
+    }
+
+    #[test]
+    fn test_promotion_to_uci() {
+        let mut promotion = Move::new(
+            Position::from_algebraic("a7").unwrap(),
+            Position::from_algebraic("a8").unwrap(),
+        );
+        promotion.promotion = Some(PieceKind::Knight);
+
+        assert_eq!(promotion.to_string(), "a7a8n");
+    }
+}
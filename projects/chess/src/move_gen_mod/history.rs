@@ -0,0 +1,210 @@
+use crate::board_mod::board::Board;
+use crate::errors::chess_error::ChessError;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+
+/// Represents the moves played from a starting position, for going back and forth through them.
+/// It keeps a snapshot of the position after every ply, so unmaking a move gives back exactly the
+/// position before it: captured pieces, castling rights, en passant square and clocks included.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct History {
+    /// Position after each ply; the first one is the starting position.
+    snapshots: Vec<Mailbox>,
+    moves: Vec<Move>,
+    ply: usize,
+}
+
+impl History {
+    /// Creates a history that starts from the position, with no moves.
+    pub fn new(start: Mailbox) -> History {
+        History {
+            snapshots: vec![start],
+            moves: Vec::new(),
+            ply: 0,
+        }
+    }
+
+    /// Creates a history that starts from the Board, with no moves.
+    pub fn from_board(board: &Board) -> History {
+        History::new(Mailbox::from_board(board))
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> &Mailbox {
+        &self.snapshots[self.ply]
+    }
+
+    /// Returns the number of moves played to reach the current position.
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Returns every move of the history, including those that were unmade and can be redone.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Plays a legal move from the current position.
+    /// Moves that were unmade are forgotten, unless the move is the next one of them, as in a redo.
+    pub fn make(&mut self, chess_move: &Move) -> Result<(), ChessError<'static>> {
+        if !self.position().legal_moves().contains(chess_move) {
+            return Err(ChessError::ILLEGAL_MOVE);
+        }
+        if self.moves.get(self.ply) == Some(chess_move) {
+            self.ply += 1;
+            return Ok(());
+        }
+
+        let next = self.position().play(chess_move);
+        self.moves.truncate(self.ply);
+        self.snapshots.truncate(self.ply + 1);
+        self.moves.push(*chess_move);
+        self.snapshots.push(next);
+        self.ply += 1;
+        Ok(())
+    }
+
+    /// Goes back one move, returning it, or None at the starting position.
+    /// The move can be played again with `redo`.
+    pub fn unmake(&mut self) -> Option<Move> {
+        self.ply = self.ply.checked_sub(1)?;
+        Some(self.moves[self.ply])
+    }
+
+    /// Plays again the last unmade move, returning it, or None if there is none.
+    pub fn redo(&mut self) -> Option<Move> {
+        let chess_move = *self.moves.get(self.ply)?;
+        self.ply += 1;
+        Some(chess_move)
+    }
+
+    /// Goes to the position after the given number of moves, back or forward.
+    pub fn go_to(&mut self, ply: usize) -> Result<(), ChessError<'static>> {
+        if ply >= self.snapshots.len() {
+            return Err(ChessError::HISTORY_PLY);
+        }
+        self.ply = ply;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    fn history(fen: &str) -> History {
+        History::from_board(&Board::from_fen(fen).unwrap())
+    }
+
+    fn make(history: &mut History, uci: &str) {
+        let chess_move = history.position().find_move(uci).unwrap();
+        history.make(&chess_move).unwrap();
+    }
+
+    /// Makes and unmakes every legal move down to the depth, checking that the board is left
+    /// exactly as it was every time.
+    fn round_trip(history: &mut History, depth: u32) {
+        if depth == 0 {
+            return;
+        }
+        let before = *history.position();
+        let fen = before.to_board().to_fen();
+        for chess_move in before.legal_moves() {
+            history.make(&chess_move).unwrap();
+            round_trip(history, depth - 1);
+            assert_eq!(history.unmake(), Some(chess_move));
+            assert_eq!(*history.position(), before);
+            assert_eq!(history.position().to_board().to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn test_make_unmake_round_trips() {
+        let mut history = history(KIWIPETE);
+        round_trip(&mut history, 2);
+        assert_eq!(history.ply(), 0);
+    }
+
+    #[test]
+    fn test_unmake_restores_captures_castling_and_en_passant() {
+        let mut history = history("r3k2r/8/8/8/1p6/8/P7/R3K2R w KQkq - 5 10");
+        let start = *history.position();
+
+        make(&mut history, "a2a4");
+        let en_passant = *history.position();
+        make(&mut history, "b4a3");
+        make(&mut history, "e1g1");
+        make(&mut history, "e8c8");
+
+        history.unmake();
+        history.unmake();
+        history.unmake();
+        assert_eq!(*history.position(), en_passant);
+        assert_eq!(
+            history.position().to_board().to_fen(),
+            "r3k2r/8/8/8/Pp6/8/8/R3K2R b KQkq a3 0 10"
+        );
+        history.unmake();
+        assert_eq!(*history.position(), start);
+        assert_eq!(history.unmake(), None);
+    }
+
+    #[test]
+    fn test_redo() {
+        let mut history = history(KIWIPETE);
+        make(&mut history, "e2a6");
+        make(&mut history, "b4c3");
+        let after = *history.position();
+        history.unmake();
+        history.unmake();
+
+        assert_eq!(history.redo().map(|m| m.to_uci()), Some("e2a6".to_string()));
+        assert_eq!(history.redo().map(|m| m.to_uci()), Some("b4c3".to_string()));
+        assert_eq!(history.redo(), None);
+        assert_eq!(*history.position(), after);
+    }
+
+    #[test]
+    fn test_make_after_unmake() {
+        let mut history = history(KIWIPETE);
+        make(&mut history, "e2a6");
+        make(&mut history, "b4c3");
+        history.go_to(0).unwrap();
+
+        make(&mut history, "e2a6");
+        assert_eq!(history.moves().len(), 2);
+        make(&mut history, "e7d8");
+        assert_eq!(history.moves().len(), 2);
+        assert_eq!(history.redo(), None);
+        assert_eq!(
+            history.make(&Move::new(
+                history.position().king(Color::White).unwrap(),
+                history.position().king(Color::Black).unwrap()
+            )),
+            Err(ChessError::ILLEGAL_MOVE)
+        );
+    }
+
+    #[test]
+    fn test_go_to() {
+        let mut history = history(KIWIPETE);
+        let start = *history.position();
+        for uci in ["e2a6", "b4c3", "b2c3", "e6d5"] {
+            make(&mut history, uci);
+        }
+        let end = *history.position();
+
+        history.go_to(1).unwrap();
+        assert_eq!(history.ply(), 1);
+        assert_eq!(history.position().side_to_move, Color::Black);
+        history.go_to(0).unwrap();
+        assert_eq!(*history.position(), start);
+        history.go_to(4).unwrap();
+        assert_eq!(*history.position(), end);
+        assert_eq!(history.go_to(5), Err(ChessError::HISTORY_PLY));
+        assert_eq!(history.ply(), 4);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::board_mod::board::Board;
+use crate::board_mod::castling_rights::CastlingRights;
+use crate::color::Color;
+use crate::move_gen_mod::chess_move::Move;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+
+/// Represents a position of a game as an array of 64 squares plus the game state.
+/// Unlike a Board, it can be copied and compared cheaply, so it is used to play and search moves.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Mailbox {
+    squares: [Option<(Color, PieceKind)>; 64],
+    /// Squares of the white and the black king, kept to find them quickly.
+    kings: [Option<Position>; 2],
+    pub side_to_move: Color,
+    pub castling: CastlingRights,
+    pub en_passant: Option<Position>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+}
+
+impl Default for Mailbox {
+    fn default() -> Self {
+        Mailbox {
+            squares: [None; 64],
+            kings: [None; 2],
+            side_to_move: Color::White,
+            castling: CastlingRights::default(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+}
+
+impl Mailbox {
+    /// Creates a Mailbox with the pieces and the game state of the Board.
+    pub fn from_board(board: &Board) -> Mailbox {
+        let mut mailbox = Mailbox {
+            side_to_move: board.side_to_move(),
+            castling: board.castling(),
+            en_passant: board.en_passant(),
+            halfmove_clock: board.halfmove_clock(),
+            fullmove_number: board.fullmove_number(),
+            ..Mailbox::default()
+        };
+        for piece in board.pieces() {
+            mailbox.set(
+                piece.get_position(),
+                Some((piece.color, piece.get_type().kind())),
+            );
+        }
+        mailbox
+    }
+
+    /// Creates a Board with the pieces and the game state of the Mailbox.
+    pub fn to_board(&self) -> Board {
+        let mut pieces = PieceList::default();
+        for (position, color, kind) in self.pieces() {
+            pieces.push(Piece::from_kind(kind, color, position));
+        }
+
+        let mut board = Board::from_checked_pieces(pieces);
+        board.set_state(
+            self.side_to_move,
+            self.castling,
+            self.en_passant,
+            self.halfmove_clock,
+            self.fullmove_number,
+        );
+        board
+    }
+
+    /// Returns the color and kind of the piece at the given position, if there is one.
+    pub fn get(&self, position: &Position) -> Option<(Color, PieceKind)> {
+        self.squares[position.index()]
+    }
+
+    /// Puts the piece at the given position, or empties it with None.
+    pub fn set(&mut self, position: &Position, piece: Option<(Color, PieceKind)>) {
+        if let Some((color, PieceKind::King)) = self.get(position) {
+            self.kings[king_slot(color)] = None;
+        }
+        if let Some((color, PieceKind::King)) = piece {
+            self.kings[king_slot(color)] = Some(*position);
+        }
+        self.squares[position.index()] = piece;
+    }
+
+    /// Returns an iterator over every piece, from a1 to h8.
+    pub fn pieces(&self) -> impl Iterator<Item = (Position, Color, PieceKind)> + '_ {
+        self.squares
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|(color, kind)| (Position::from_index(i), color, kind)))
+    }
+
+    /// Returns the position of the king of the given color, if there is one.
+    /// If there are more kings of that color, it is the one placed last.
+    pub fn king(&self, color: Color) -> Option<Position> {
+        self.kings[king_slot(color)]
+    }
+
+    /// Returns the position after playing the move. The move is expected to come from `legal_moves`.
+    pub fn play(&self, chess_move: &Move) -> Mailbox {
+        let mut next = *self;
+        let (color, kind) = match self.get(&chess_move.from) {
+            Some(piece) => piece,
+            None => return next,
+        };
+        let rank = color.back_rank();
+        next.en_passant = None;
+        next.halfmove_clock += 1;
+
+        if let Some(side) = chess_move.castling {
+            let rook_file = self.castling.get(color, side).unwrap_or_default();
+            next.set(&chess_move.from, None);
+            next.set(
+                &Position {
+                    x: rook_file,
+                    y: rank,
+                },
+                None,
+            );
+            next.set(&chess_move.to, Some((color, PieceKind::King)));
+            let rook_square = Position {
+                x: side.rook_destination(),
+                y: rank,
+            };
+            next.set(&rook_square, Some((color, PieceKind::Rook)));
+            next.castling.remove_color(color);
+        } else {
+            let captured = self.get(&chess_move.to);
+            if kind == PieceKind::Pawn && chess_move.from.x != chess_move.to.x && captured.is_none()
+            {
+                let passed = Position {
+                    x: chess_move.to.x,
+                    y: chess_move.from.y,
+                };
+                next.set(&passed, None);
+            }
+            if kind == PieceKind::Pawn || captured.is_some() {
+                next.halfmove_clock = 0;
+            }
+            if let Some((captured_color, PieceKind::Rook)) = captured {
+                if chess_move.to.y == captured_color.back_rank() {
+                    next.castling.remove_rook(captured_color, chess_move.to.x);
+                }
+            }
+
+            next.set(&chess_move.from, None);
+            next.set(
+                &chess_move.to,
+                Some((color, chess_move.promotion.unwrap_or(kind))),
+            );
+
+            match kind {
+                PieceKind::King => next.castling.remove_color(color),
+                PieceKind::Rook if chess_move.from.y == rank => {
+                    next.castling.remove_rook(color, chess_move.from.x)
+                }
+                PieceKind::Pawn if (chess_move.to.y - chess_move.from.y).abs() == 2 => {
+                    next.en_passant = Some(Position {
+                        x: chess_move.from.x,
+                        y: (chess_move.from.y + chess_move.to.y) / 2,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if color == Color::Black {
+            next.fullmove_number += 1;
+        }
+        next.side_to_move = color.opposite();
+        next
+    }
+}
+
+/// Returns where the king of the color is kept.
+fn king_slot(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(name: &str) -> Position {
+        Position::from_algebraic(name).unwrap()
+    }
+
+    fn mailbox(fen: &str) -> Mailbox {
+        Mailbox::from_board(&Board::from_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn test_board_round_trip() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQq d6 2 12";
+
+        assert_eq!(mailbox(fen).to_board().to_fen(), fen);
+    }
+
+    #[test]
+    fn test_king() {
+        let position = mailbox("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+
+        assert_eq!(position.king(Color::Black), Some(square("e8")));
+        assert_eq!(Mailbox::default().king(Color::White), None);
+    }
+
+    #[test]
+    fn test_play_double_push() {
+        let start = mailbox("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let next = start.play(&Move::new(square("e2"), square("e4")));
+
+        assert_eq!(
+            next.to_board().to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
+
+    #[test]
+    fn test_play_en_passant() {
+        let position = mailbox("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 3");
+        let next = position.play(&Move::new(square("e5"), square("d6")));
+
+        assert_eq!(next.get(&square("d5")), None);
+        assert_eq!(
+            next.get(&square("d6")),
+            Some((Color::White, PieceKind::Pawn))
+        );
+    }
+
+    #[test]
+    fn test_rook_capture_removes_castling() {
+        let position = mailbox("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let next = position.play(&Move::new(square("a1"), square("a8")));
+
+        assert_eq!(
+            next.to_board().to_fen(),
+            "R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 1"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod chess_move;
+pub mod history;
+pub mod mailbox;
+pub mod move_gen;
+pub mod san;
@@ -0,0 +1,377 @@
+use crate::board_mod::castling_rights::CastlingSide;
+use crate::color::Color;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+
+const KNIGHT_STEPS: [(i32, i32); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_STEPS: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+];
+
+impl Mailbox {
+    /// Returns every legal move of the side to move.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let color = self.side_to_move;
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|m| !self.play(m).in_check(color))
+            .collect()
+    }
+
+    /// Returns every move of the side to move that follows the pieces' rules,
+    /// even if it leaves its own king in check.
+    pub fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let color = self.side_to_move;
+        let mut moves = Vec::new();
+        for (from, c, kind) in self.pieces() {
+            if c != color {
+                continue;
+            }
+            match kind {
+                PieceKind::Pawn => self.pawn_moves(from, color, &mut moves),
+                PieceKind::Knight => self.step_moves(from, color, &KNIGHT_STEPS, &mut moves),
+                PieceKind::King => self.step_moves(from, color, &KING_STEPS, &mut moves),
+                PieceKind::Rook => self.slide_moves(from, color, &ROOK_DIRECTIONS, &mut moves),
+                PieceKind::Bishop => self.slide_moves(from, color, &BISHOP_DIRECTIONS, &mut moves),
+                PieceKind::Queen => {
+                    self.slide_moves(from, color, &ROOK_DIRECTIONS, &mut moves);
+                    self.slide_moves(from, color, &BISHOP_DIRECTIONS, &mut moves);
+                }
+            }
+        }
+        self.castling_moves(color, &mut moves);
+        moves
+    }
+
+    /// Returns true if a piece of the given color attacks the square.
+    pub fn is_attacked(&self, square: &Position, by: Color) -> bool {
+        let holds = |position: Option<Position>, kinds: &[PieceKind]| {
+            position
+                .and_then(|p| self.get(&p))
+                .is_some_and(|(c, kind)| c == by && kinds.contains(&kind))
+        };
+
+        let pawn_rank = -by.pawn_direction();
+        if holds(square.offset(1, pawn_rank), &[PieceKind::Pawn])
+            || holds(square.offset(-1, pawn_rank), &[PieceKind::Pawn])
+        {
+            return true;
+        }
+        if KNIGHT_STEPS
+            .iter()
+            .any(|(dx, dy)| holds(square.offset(*dx, *dy), &[PieceKind::Knight]))
+        {
+            return true;
+        }
+        if KING_STEPS
+            .iter()
+            .any(|(dx, dy)| holds(square.offset(*dx, *dy), &[PieceKind::King]))
+        {
+            return true;
+        }
+
+        let straight = [PieceKind::Rook, PieceKind::Queen];
+        let diagonal = [PieceKind::Bishop, PieceKind::Queen];
+        ROOK_DIRECTIONS
+            .iter()
+            .any(|d| holds(self.first_piece(square, *d), &straight))
+            || BISHOP_DIRECTIONS
+                .iter()
+                .any(|d| holds(self.first_piece(square, *d), &diagonal))
+    }
+
+    /// Returns true if the king of the given color is attacked.
+    pub fn in_check(&self, color: Color) -> bool {
+        self.king(color)
+            .is_some_and(|king| self.is_attacked(&king, color.opposite()))
+    }
+
+    /// Returns true if the side to move is in check and has no legal moves.
+    pub fn is_checkmate(&self) -> bool {
+        self.in_check(self.side_to_move) && self.legal_moves().is_empty()
+    }
+
+    /// Returns true if the side to move is not in check but has no legal moves.
+    pub fn is_stalemate(&self) -> bool {
+        !self.in_check(self.side_to_move) && self.legal_moves().is_empty()
+    }
+
+    /// Returns the legal move written in UCI notation, like "e2e4" or "e7e8q".
+    /// Castling can also be written as the king taking its own rook, as Chess960 UCI does.
+    pub fn find_move(&self, uci: &str) -> Option<Move> {
+        let uci = uci.trim().to_ascii_lowercase();
+        self.legal_moves().into_iter().find(|m| {
+            if m.to_uci() == uci {
+                return true;
+            }
+            match m.castling {
+                Some(side) => {
+                    let rook = self.castling.get(self.side_to_move, side);
+                    rook.is_some_and(|x| {
+                        let rook_square = Position { x, y: m.from.y };
+                        format!("{}{}", m.from, rook_square) == uci
+                    })
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Returns the position of the first piece found from the square in the given direction.
+    fn first_piece(&self, square: &Position, (dx, dy): (i32, i32)) -> Option<Position> {
+        let mut current = square.offset(dx, dy);
+        while let Some(position) = current {
+            if self.get(&position).is_some() {
+                return Some(position);
+            }
+            current = position.offset(dx, dy);
+        }
+        None
+    }
+
+    /// Returns true if the square can be entered by a piece of the given color: it is empty or has a rival.
+    fn can_enter(&self, square: &Position, color: Color) -> bool {
+        self.get(square).is_none_or(|(c, _)| c != color)
+    }
+
+    fn step_moves(
+        &self,
+        from: Position,
+        color: Color,
+        steps: &[(i32, i32)],
+        moves: &mut Vec<Move>,
+    ) {
+        for (dx, dy) in steps {
+            if let Some(to) = from.offset(*dx, *dy) {
+                if self.can_enter(&to, color) {
+                    moves.push(Move::new(from, to));
+                }
+            }
+        }
+    }
+
+    fn slide_moves(
+        &self,
+        from: Position,
+        color: Color,
+        directions: &[(i32, i32)],
+        moves: &mut Vec<Move>,
+    ) {
+        for (dx, dy) in directions {
+            let mut current = from.offset(*dx, *dy);
+            while let Some(to) = current {
+                match self.get(&to) {
+                    None => moves.push(Move::new(from, to)),
+                    Some((c, _)) => {
+                        if c != color {
+                            moves.push(Move::new(from, to));
+                        }
+                        break;
+                    }
+                }
+                current = to.offset(*dx, *dy);
+            }
+        }
+    }
+
+    fn pawn_moves(&self, from: Position, color: Color, moves: &mut Vec<Move>) {
+        let direction = color.pawn_direction();
+        let start_rank = color.back_rank() + direction;
+        let mut targets = Vec::new();
+
+        if let Some(one) = from.offset(0, direction) {
+            if self.get(&one).is_none() {
+                targets.push(one);
+                if let Some(two) = one.offset(0, direction) {
+                    if from.y == start_rank && self.get(&two).is_none() {
+                        targets.push(two);
+                    }
+                }
+            }
+        }
+        for dx in [-1, 1] {
+            if let Some(to) = from.offset(dx, direction) {
+                let captures = self.get(&to).is_some_and(|(c, _)| c != color);
+                if captures || self.en_passant == Some(to) {
+                    targets.push(to);
+                }
+            }
+        }
+
+        for to in targets {
+            if to.y == color.opposite().back_rank() {
+                for kind in PROMOTIONS {
+                    let mut promotion = Move::new(from, to);
+                    promotion.promotion = Some(kind);
+                    moves.push(promotion);
+                }
+            } else {
+                moves.push(Move::new(from, to));
+            }
+        }
+    }
+
+    /// Adds the castlings allowed by the castling rights, with Chess960 rules: the squares between
+    /// the king, the rook and their destinations must be empty, and the king can not castle out of,
+    /// through or into check.
+    fn castling_moves(&self, color: Color, moves: &mut Vec<Move>) {
+        let rank = color.back_rank();
+        let king = match self.king(color) {
+            Some(king) if king.y == rank => king,
+            _ => return,
+        };
+
+        for side in CastlingSide::ALL {
+            let rook_file = match self.castling.get(color, side) {
+                Some(file) => file,
+                None => continue,
+            };
+            let rook = Position {
+                x: rook_file,
+                y: rank,
+            };
+            if self.get(&rook) != Some((color, PieceKind::Rook)) {
+                continue;
+            }
+
+            let king_to = side.king_destination();
+            let rook_to = side.rook_destination();
+            let files = [king.x, king_to, rook_file, rook_to];
+            let (low, high) = (
+                *files.iter().min().unwrap_or(&0),
+                *files.iter().max().unwrap_or(&7),
+            );
+            let path_is_empty = (low..=high).all(|x| {
+                x == king.x || x == rook_file || self.get(&Position { x, y: rank }).is_none()
+            });
+
+            let (from, to) = (king.x.min(king_to), king.x.max(king_to));
+            let path_is_safe =
+                (from..=to).all(|x| !self.is_attacked(&Position { x, y: rank }, color.opposite()));
+
+            if path_is_empty && path_is_safe {
+                moves.push(Move {
+                    from: king,
+                    to: Position {
+                        x: king_to,
+                        y: rank,
+                    },
+                    promotion: None,
+                    castling: Some(side),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+    use crate::chess960_mod::chess960::chess960_board;
+
+    fn mailbox(fen: &str) -> Mailbox {
+        Mailbox::from_board(&Board::from_fen(fen).unwrap())
+    }
+
+    fn perft(position: &Mailbox, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        position
+            .legal_moves()
+            .iter()
+            .map(|m| perft(&position.play(m), depth - 1))
+            .sum()
+    }
+
+    #[test]
+    fn test_start_position_perft() {
+        let start = mailbox("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        assert_eq!(perft(&start, 1), 20);
+        assert_eq!(perft(&start, 2), 400);
+        assert_eq!(perft(&start, 3), 8902);
+    }
+
+    #[test]
+    fn test_tricky_position_perft() {
+        // "Kiwipete", a position full of castlings, pins, en passant and promotions.
+        let position =
+            mailbox("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+
+        assert_eq!(perft(&position, 1), 48);
+        assert_eq!(perft(&position, 2), 2039);
+    }
+
+    #[test]
+    fn test_chess960_perft() {
+        // In BBQNNRKR only the pawns and the knights on d1 and e1 can move at first.
+        let position = Mailbox::from_board(&chess960_board(0).unwrap());
+
+        assert_eq!(perft(&position, 1), 20);
+        assert_eq!(perft(&position, 2), 400);
+    }
+
+    #[test]
+    fn test_chess960_castling_king_does_not_move() {
+        // The king already stands on g1 and the rook goes from h1 to f1.
+        let position = mailbox("4k3/8/8/8/8/8/8/R5KR w K - 0 1");
+        let castling = position.find_move("g1h1");
+
+        assert!(castling.is_some());
+        let after = position.play(&castling.unwrap());
+        assert_eq!(after.to_board().to_fen(), "4k3/8/8/8/8/8/8/R4RK1 b - - 1 1");
+    }
+
+    #[test]
+    fn test_castling_through_check() {
+        let position = mailbox("4kr2/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+
+        assert_eq!(position.find_move("e1g1"), None);
+        assert!(position.find_move("e1c1").is_some());
+    }
+
+    #[test]
+    fn test_checkmate_and_stalemate() {
+        assert!(mailbox("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").is_checkmate());
+        assert!(mailbox("7k/8/6QK/8/8/8/8/8 b - - 0 1").is_stalemate());
+        assert!(!mailbox("7k/8/6QK/8/8/8/8/8 w - - 0 1").is_stalemate());
+    }
+
+    #[test]
+    fn test_is_attacked() {
+        let position = mailbox("4k3/8/8/3p4/8/8/8/R3K3 w - - 0 1");
+        let square = |name| Position::from_algebraic(name).unwrap();
+
+        assert!(position.is_attacked(&square("a8"), Color::White));
+        assert!(position.is_attacked(&square("e4"), Color::Black));
+        assert!(!position.is_attacked(&square("d4"), Color::Black));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::board_mod::castling_rights::CastlingSide;
+use crate::color::Color;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+
+impl Mailbox {
+    /// Returns the legal move of the position in Standard Algebraic Notation, like "Nf3", "exd5",
+    /// "O-O" or "e8=Q+".
+    pub fn to_san(&self, chess_move: &Move) -> String {
+        let mut san = match chess_move.castling {
+            Some(CastlingSide::King) => "O-O".to_string(),
+            Some(CastlingSide::Queen) => "O-O-O".to_string(),
+            None => self.san_without_check(chess_move),
+        };
+
+        let after = self.play(chess_move);
+        if after.is_checkmate() {
+            san.push('#');
+        } else if after.in_check(after.side_to_move) {
+            san.push('+');
+        }
+        san
+    }
+
+    /// Returns the legal move written in Standard Algebraic Notation.
+    /// The check and mate marks, and annotations like "!" or "?", are not needed.
+    pub fn find_san(&self, san: &str) -> Option<Move> {
+        let clean = |text: &str| -> String {
+            text.trim()
+                .trim_end_matches(['+', '#', '!', '?'])
+                .replace('0', "O")
+        };
+        let wanted = clean(san);
+        self.legal_moves()
+            .into_iter()
+            .find(|m| clean(&self.to_san(m)) == wanted)
+    }
+
+    fn san_without_check(&self, chess_move: &Move) -> String {
+        let kind = match self.get(&chess_move.from) {
+            Some((_, kind)) => kind,
+            None => return chess_move.to_uci(),
+        };
+        let capture = self.get(&chess_move.to).is_some()
+            || (kind == PieceKind::Pawn && chess_move.from.x != chess_move.to.x);
+
+        let mut san = String::new();
+        if kind == PieceKind::Pawn {
+            if capture {
+                san.push_str(&chess_move.from.to_algebraic()[..1]);
+            }
+        } else {
+            san.push(kind.fen_char(Color::White));
+            san.push_str(&self.disambiguation(chess_move, kind));
+        }
+        if capture {
+            san.push('x');
+        }
+        san.push_str(&chess_move.to.to_algebraic());
+        if let Some(promotion) = chess_move.promotion {
+            san.push('=');
+            san.push(promotion.fen_char(Color::White));
+        }
+        san
+    }
+
+    /// Returns the file, the rank or the square of the origin, whichever is needed to tell the move
+    /// apart from moves of other pieces of the same kind to the same square.
+    fn disambiguation(&self, chess_move: &Move, kind: PieceKind) -> String {
+        let rivals: Vec<Move> = self
+            .legal_moves()
+            .into_iter()
+            .filter(|m| {
+                m.to == chess_move.to
+                    && m.from != chess_move.from
+                    && m.castling.is_none()
+                    && self.get(&m.from).map(|(_, k)| k) == Some(kind)
+            })
+            .collect();
+        let square = chess_move.from.to_algebraic();
+        if rivals.is_empty() {
+            String::new()
+        } else if rivals.iter().all(|m| m.from.x != chess_move.from.x) {
+            square[..1].to_string()
+        } else if rivals.iter().all(|m| m.from.y != chess_move.from.y) {
+            square[1..].to_string()
+        } else {
+            square
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board_mod::board::Board;
+    use crate::move_gen_mod::mailbox::Mailbox;
+
+    fn san(fen: &str, uci: &str) -> String {
+        let position = Mailbox::from_board(&Board::from_fen(fen).unwrap());
+        position.to_san(&position.find_move(uci).unwrap())
+    }
+
+    #[test]
+    fn test_simple_moves() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+        assert_eq!(san(start, "e2e4"), "e4");
+        assert_eq!(san(start, "g1f3"), "Nf3");
+    }
+
+    #[test]
+    fn test_captures_and_promotions() {
+        assert_eq!(san("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", "e4d5"), "exd5");
+        assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+        assert_eq!(san("3r2k1/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8n"), "exd8=N");
+        assert_eq!(san("6k1/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), "b8=Q+");
+    }
+
+    #[test]
+    fn test_disambiguation() {
+        assert_eq!(san("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1", "a1d1"), "Rad1");
+        assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+        assert_eq!(san("7k/8/8/8/8/2Q1Q3/8/2Q1K3 w - - 0 1", "c3d2"), "Qc3d2");
+    }
+
+    #[test]
+    fn test_castling_and_mate() {
+        assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"), "O-O");
+        assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8c8"), "O-O-O");
+        assert_eq!(san("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"), "Ra8#");
+    }
+
+    #[test]
+    fn test_find_san() {
+        let position =
+            Mailbox::from_board(&Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap());
+
+        assert_eq!(position.find_san("0-0"), position.find_move("e1g1"));
+        assert_eq!(position.find_san("Rb1!"), position.find_move("a1b1"));
+        assert_eq!(position.find_san("Rxh8+"), position.find_move("h1h8"));
+        assert_eq!(position.find_san("Qd4"), None);
+    }
+}
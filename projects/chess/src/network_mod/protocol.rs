@@ -0,0 +1,134 @@
+//! Line protocol of the chess server. Every message is one line of UTF-8 text ending in '\n',
+//! made of a command in uppercase and its arguments separated by spaces.
+//!
+//! Client to server:
+//! - `MOVE <move>`: plays a move, written in UCI (`e2e4`, `e7e8q`) or SAN (`Nf3`, `O-O`).
+//! - `RESIGN`: gives up the game. Closing the connection does the same.
+//!
+//! Server to client:
+//! - `WAITING`: the client is connected and waits for a rival.
+//! - `COLOR <white|black>`: a rival was found; the game starts and this is the client's color.
+//! - `BOARD <fen>`: the position, in FEN. Sent when the game starts and after every move.
+//! - `MOVED <uci> <san>`: a move was played, by either player.
+//! - `ERROR <message>`: the last message of this client was rejected; the game goes on.
+//! - `RESULT <1-0|0-1|1/2-1/2> <description>`: the game is over. The server closes the connection.
+
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents a message sent by a client.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ClientMessage {
+    Move(String),
+    Resign,
+}
+
+/// Represents a message sent by the server.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ServerMessage {
+    Waiting,
+    Color(Color),
+    Board(String),
+    Moved { uci: String, san: String },
+    Error(String),
+    Result { score: String, description: String },
+}
+
+impl FromStr for ClientMessage {
+    type Err = ChessError<'static>;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let line = line.trim();
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        match (command, argument.trim()) {
+            ("MOVE", text) if !text.is_empty() => Ok(ClientMessage::Move(text.to_string())),
+            ("RESIGN", "") => Ok(ClientMessage::Resign),
+            _ => Err(ChessError::UNKNOWN_COMMAND),
+        }
+    }
+}
+
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientMessage::Move(text) => write!(f, "MOVE {}", text),
+            ClientMessage::Resign => write!(f, "RESIGN"),
+        }
+    }
+}
+
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerMessage::Waiting => write!(f, "WAITING"),
+            ServerMessage::Color(Color::White) => write!(f, "COLOR white"),
+            ServerMessage::Color(Color::Black) => write!(f, "COLOR black"),
+            ServerMessage::Board(fen) => write!(f, "BOARD {}", fen),
+            ServerMessage::Moved { uci, san } => write!(f, "MOVED {} {}", uci, san),
+            ServerMessage::Error(message) => write!(f, "ERROR {}", message),
+            ServerMessage::Result { score, description } => {
+                write!(f, "RESULT {} {}", score, description)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_messages() {
+        assert_eq!(
+            "MOVE e2e4".parse(),
+            Ok(ClientMessage::Move("e2e4".to_string()))
+        );
+        assert_eq!(
+            "MOVE  O-O \r".parse(),
+            Ok(ClientMessage::Move("O-O".to_string()))
+        );
+        assert_eq!("RESIGN".parse(), Ok(ClientMessage::Resign));
+        for line in ["", "MOVE", "move e2e4", "RESIGN now", "HELLO"] {
+            assert_eq!(
+                line.parse::<ClientMessage>(),
+                Err(ChessError::UNKNOWN_COMMAND)
+            );
+        }
+    }
+
+    #[test]
+    fn test_client_messages_round_trip() {
+        for message in [
+            ClientMessage::Move("Nf3".to_string()),
+            ClientMessage::Resign,
+        ] {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_server_messages() {
+        assert_eq!(
+            ServerMessage::Color(Color::Black).to_string(),
+            "COLOR black"
+        );
+        assert_eq!(
+            ServerMessage::Moved {
+                uci: "g1f3".to_string(),
+                san: "Nf3".to_string()
+            }
+            .to_string(),
+            "MOVED g1f3 Nf3"
+        );
+        assert_eq!(
+            ServerMessage::Result {
+                score: "1-0".to_string(),
+                description: "Black resigns".to_string()
+            }
+            .to_string(),
+            "RESULT 1-0 Black resigns"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use crate::board_mod::board::Board;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::game_mod::game::{Game, GameResult};
+use crate::network_mod::protocol::{ClientMessage, ServerMessage};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+
+/// Accepts clients forever and pairs them in order of arrival: the first of each pair plays white.
+/// Every game is played in its own thread, so the server keeps accepting clients meanwhile.
+/// A client who could not be accepted is logged and skipped, and a waiting client who left is
+/// dropped, so the next one waits in its place.
+/// Games start from the position written in FEN; it fails right away if it is not valid.
+pub fn run_server(listener: TcpListener, start: &str) -> io::Result<()> {
+    Board::from_fen(start).map_err(invalid_start)?;
+    let mut waiting: Option<TcpStream> = None;
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("Could not accept a client: {}", e);
+                continue;
+            }
+        };
+        waiting = waiting.filter(is_connected);
+        match waiting.take() {
+            None => {
+                if send(&mut stream, &ServerMessage::Waiting).is_ok() {
+                    waiting = Some(stream);
+                }
+            }
+            Some(white) => {
+                let start = Board::from_fen(start).map_err(invalid_start)?;
+                thread::spawn(move || play_match(white, stream, start));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Plays a game between two connected clients, following the line protocol.
+/// Moves are checked with the move generator; only legal moves are relayed to both players.
+/// Returns the result once the game is over, after sending it to both players.
+pub fn play_match(white: TcpStream, black: TcpStream, start: Board) -> io::Result<GameResult> {
+    let (sender, receiver) = mpsc::channel();
+    for (color, stream) in [(Color::White, &white), (Color::Black, &black)] {
+        let reader = BufReader::new(stream.try_clone()?);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for line in reader.lines().map_while(Result::ok) {
+                if sender.send((color, Some(line))).is_err() {
+                    return;
+                }
+            }
+            // The connection was closed.
+            let _ = sender.send((color, None));
+        });
+    }
+
+    let mut players = [white, black];
+    let mut game = Game::new(start);
+    let board = ServerMessage::Board(game.position().to_board().to_fen());
+    let _ = send(&mut players[0], &ServerMessage::Color(Color::White));
+    let _ = send(&mut players[1], &ServerMessage::Color(Color::Black));
+    broadcast(&mut players, &board);
+
+    while game.result().is_none() {
+        let (color, line) = match receiver.recv() {
+            Ok(event) => event,
+            Err(_) => break,
+        };
+        let line = match line {
+            Some(line) => line,
+            None => {
+                game.resign(color);
+                break;
+            }
+        };
+
+        let answer = match line.parse::<ClientMessage>() {
+            Ok(ClientMessage::Resign) => {
+                game.resign(color);
+                None
+            }
+            Ok(ClientMessage::Move(_)) if game.position().side_to_move != color => {
+                Some(ChessError::NOT_YOUR_TURN)
+            }
+            Ok(ClientMessage::Move(text)) => match game.play(&text) {
+                Ok(played) => {
+                    let moved = ServerMessage::Moved {
+                        uci: played.chess_move.to_uci(),
+                        san: played.san.clone(),
+                    };
+                    broadcast(&mut players, &moved);
+                    let board = ServerMessage::Board(game.position().to_board().to_fen());
+                    broadcast(&mut players, &board);
+                    None
+                }
+                Err(e) => Some(e),
+            },
+            Err(e) => Some(e),
+        };
+        if let Some(e) = answer {
+            let _ = send(
+                &mut players[slot(color)],
+                &ServerMessage::Error(e.message.to_string()),
+            );
+        }
+    }
+
+    let result = *game
+        .result()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "game was not finished"))?;
+    let message = ServerMessage::Result {
+        score: result.score().to_string(),
+        description: result.to_string(),
+    };
+    broadcast(&mut players, &message);
+    for player in &players {
+        let _ = player.shutdown(Shutdown::Both);
+    }
+    Ok(result)
+}
+
+/// Tells whether the peer is still connected, without taking anything it sent.
+/// The stream is peeked without blocking: a closed connection reads 0 bytes right away.
+fn is_connected(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let connected = match stream.peek(&mut [0; 1]) {
+        Ok(read) => read > 0,
+        Err(e) => e.kind() == io::ErrorKind::WouldBlock,
+    };
+    stream.set_nonblocking(false).is_ok() && connected
+}
+
+fn invalid_start(e: ChessError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, e.message)
+}
+
+/// Sends a message to every player. A player who left just misses it.
+fn broadcast(players: &mut [TcpStream; 2], message: &ServerMessage) {
+    for player in players.iter_mut() {
+        let _ = send(player, message);
+    }
+}
+
+fn send(stream: &mut TcpStream, message: &ServerMessage) -> io::Result<()> {
+    stream.write_all(format!("{}\n", message).as_bytes())
+}
+
+fn slot(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_mod::game::Termination;
+    use std::io::Lines;
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    struct Client {
+        stream: TcpStream,
+        lines: Lines<BufReader<TcpStream>>,
+    }
+
+    impl Client {
+        fn connect(address: &str) -> Client {
+            let stream = TcpStream::connect(address).unwrap();
+            let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+            Client { stream, lines }
+        }
+
+        fn send(&mut self, line: &str) {
+            self.stream
+                .write_all(format!("{}\n", line).as_bytes())
+                .unwrap();
+        }
+
+        fn receive(&mut self) -> String {
+            self.lines.next().unwrap().unwrap()
+        }
+    }
+
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || run_server(listener, START));
+        address
+    }
+
+    /// Connects two clients and reads the messages that start the game.
+    fn pair(address: &str) -> (Client, Client) {
+        let mut white = Client::connect(address);
+        assert_eq!(white.receive(), "WAITING");
+        let mut black = Client::connect(address);
+
+        assert_eq!(white.receive(), "COLOR white");
+        assert_eq!(black.receive(), "COLOR black");
+        for client in [&mut white, &mut black] {
+            assert_eq!(client.receive(), format!("BOARD {}", START));
+        }
+        (white, black)
+    }
+
+    #[test]
+    fn test_moves_are_relayed_until_mate() {
+        let address = start_server();
+        let (mut white, mut black) = pair(&address);
+
+        white.send("MOVE f2f3");
+        for client in [&mut white, &mut black] {
+            assert_eq!(client.receive(), "MOVED f2f3 f3");
+            assert_eq!(
+                client.receive(),
+                "BOARD rnbqkbnr/pppppppp/8/8/8/5P2/PPPPP1PP/RNBQKBNR b KQkq - 0 1"
+            );
+        }
+        for (mover, text) in [(1, "e5"), (0, "g4"), (1, "Qh4#")] {
+            let sender = if mover == 0 { &mut white } else { &mut black };
+            sender.send(&format!("MOVE {}", text));
+            for client in [&mut white, &mut black] {
+                assert!(client.receive().starts_with("MOVED "));
+                assert!(client.receive().starts_with("BOARD "));
+            }
+        }
+
+        for client in [&mut white, &mut black] {
+            assert_eq!(client.receive(), "RESULT 0-1 Black wins by checkmate");
+            assert!(client.lines.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_illegal_messages_are_rejected() {
+        let address = start_server();
+        let (mut white, mut black) = pair(&address);
+
+        black.send("MOVE e7e5");
+        assert_eq!(black.receive(), "ERROR It is not your turn");
+        white.send("MOVE e2e5");
+        assert_eq!(white.receive(), "ERROR Move is not legal in this position");
+        white.send("HELLO");
+        assert_eq!(
+            white.receive(),
+            format!("ERROR {}", ChessError::UNKNOWN_COMMAND.message)
+        );
+
+        white.send("MOVE e4");
+        assert_eq!(black.receive(), "MOVED e2e4 e4");
+    }
+
+    #[test]
+    fn test_resign_and_disconnection() {
+        let address = start_server();
+
+        let (mut white, mut black) = pair(&address);
+        black.send("RESIGN");
+        for client in [&mut white, &mut black] {
+            assert_eq!(client.receive(), "RESULT 1-0 Black resigns");
+        }
+
+        let (white, mut black) = pair(&address);
+        drop(white);
+        assert_eq!(black.receive(), "RESULT 0-1 White resigns");
+    }
+
+    #[test]
+    fn test_waiting_client_who_left_is_dropped() {
+        let address = start_server();
+
+        let mut gone = Client::connect(&address);
+        assert_eq!(gone.receive(), "WAITING");
+        drop(gone);
+
+        let (mut white, mut black) = pair(&address);
+        white.send("MOVE e4");
+        assert_eq!(black.receive(), "MOVED e2e4 e4");
+    }
+
+    #[test]
+    fn test_play_match_returns_the_result() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut white = Client::connect(&address.to_string());
+        let (white_stream, _) = listener.accept().unwrap();
+        let black = Client::connect(&address.to_string());
+        let (black_stream, _) = listener.accept().unwrap();
+
+        white.send("RESIGN");
+        let result =
+            play_match(white_stream, black_stream, Board::from_fen(START).unwrap()).unwrap();
+        assert_eq!(result.termination, Termination::Resignation(Color::White));
+        drop(black);
+    }
+}
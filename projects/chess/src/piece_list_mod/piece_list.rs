@@ -1,11 +1,16 @@
+use crate::exchange_mod::static_exchange::StaticExchange;
 use crate::piece_mod::piece::Piece;
+use crate::position_mod::position::Position;
 use crate::results::fight_result::FightResult;
 
-/// PieceList is a list of pieces that can fight each other. Pieces are given by input. 
-
+/// PieceList is a list of pieces that can fight each other. Pieces are given by input.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct PieceList {
     list: Vec<Piece>,
-    pub len: i32,
 }
 
 impl Default for PieceList {
@@ -14,29 +19,59 @@ impl Default for PieceList {
     }
 }
 
+impl<'a> IntoIterator for &'a PieceList {
+    type Item = &'a Piece;
+    type IntoIter = std::slice::Iter<'a, Piece>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl PieceList {
     /// Initializes the struct
     pub fn init() -> PieceList {
-        PieceList {
-            list: Vec::new(),
-            len: 0,
-        }
+        PieceList { list: Vec::new() }
     }
 
     /// Pushes a piece to the end of the list
     pub fn push(&mut self, piece: Piece) {
         self.list.push(piece);
-        self.len += 1;
     }
 
-    /// Returns the first piece.
-    pub fn first(&self) -> &Piece {
-        &self.list[0]
+    /// Returns the number of pieces in the list
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns true if the list has no pieces
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns the piece at the given index, if there is one.
+    pub fn get(&self, index: usize) -> Option<&Piece> {
+        self.list.get(index)
+    }
+
+    /// Returns the first piece, if there is one.
+    pub fn first(&self) -> Option<&Piece> {
+        self.get(0)
+    }
+
+    /// Returns the second piece, if there is one.
+    pub fn second(&self) -> Option<&Piece> {
+        self.get(1)
     }
 
-    /// Returns the second piece
-    pub fn second(&self) -> &Piece {
-        &self.list[1]
+    /// Returns an iterator over the pieces, in the order they were pushed.
+    pub fn iter(&self) -> std::slice::Iter<'_, Piece> {
+        self.list.iter()
+    }
+
+    /// Evaluates the capture sequence on the given square. Returns None if the square is empty.
+    pub fn static_exchange(&self, target: &Position) -> Option<StaticExchange<'_>> {
+        StaticExchange::new(self, target)
     }
 
     /// Makes the first two pieces in the list fight each other and returns the result. This result can be:
@@ -44,9 +79,15 @@ impl PieceList {
     /// - B: white wins
     /// - N: black wins
     /// - P: neither wins
-    pub fn fight(&self) -> FightResult {
-        let first_movement = self.first().can_capture(self.second());
-        let second_movement = self.second().can_capture(self.first());
+    ///
+    /// With less than two pieces there is no fight, so neither wins.
+    pub fn fight(&self) -> FightResult<'_> {
+        let (first, second) = match (self.first(), self.second()) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return FightResult::NEITHER_WIN,
+        };
+        let first_movement = first.can_capture(second);
+        let second_movement = second.can_capture(first);
 
         let white_wins = first_movement.white_wins() || second_movement.white_wins();
         let black_wins = first_movement.black_wins() || second_movement.black_wins();
@@ -72,33 +113,34 @@ mod tests {
     fn test_create_piece_list() {
         let piece_list = PieceList::default();
 
-        assert_eq!(piece_list.len, 0);
+        assert_eq!(piece_list.len(), 0);
+        assert!(piece_list.is_empty());
     }
 
     #[test]
     fn test_push_piece() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("r", 2, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
 
-        assert_eq!(piece_list.len, 2);
+        assert_eq!(piece_list.len(), 2);
     }
 
     #[test]
     fn test_first_piece() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("r", 2, 1).unwrap();
 
         let expected_info = piece1.get_info();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
 
-        let first_info = piece_list.first().get_info();
+        let first_info = piece_list.first().unwrap().get_info();
 
         assert_eq!(&first_info, &expected_info);
     }
@@ -106,25 +148,43 @@ mod tests {
     #[test]
     fn test_second_piece() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("r", 2, 1).unwrap();
 
         let expected_info = piece2.get_info();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
 
-        let first_info = piece_list.second().get_info();
+        let first_info = piece_list.second().unwrap().get_info();
 
         assert_eq!(&first_info, &expected_info);
     }
 
+    #[test]
+    fn test_first_piece_empty_list() {
+        let piece_list = PieceList::init();
+
+        assert!(piece_list.first().is_none());
+        assert!(piece_list.second().is_none());
+    }
+
+    #[test]
+    fn test_fight_single_piece_neither_win() {
+        let mut piece_list = PieceList::init();
+        piece_list.push(Piece::new("R", 3, 3).unwrap());
+
+        let res = piece_list.fight();
+
+        assert_eq!(res.output, FightResult::NEITHER_WIN.output);
+    }
+
     // King and Queen
     #[test]
     fn test_fight_black_king_white_queen_both_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 3, 3).unwrap();
-        let piece2 = Piece::new(&"d", 3, 4).unwrap();
+        let piece1 = Piece::new("R", 3, 3).unwrap();
+        let piece2 = Piece::new("d", 3, 4).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -138,8 +198,8 @@ mod tests {
     #[test]
     fn test_fight_black_king_white_bishop_white_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 3, 3).unwrap();
-        let piece2 = Piece::new(&"a", 1, 1).unwrap();
+        let piece1 = Piece::new("R", 3, 3).unwrap();
+        let piece2 = Piece::new("a", 1, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -153,8 +213,8 @@ mod tests {
     #[test]
     fn test_fight_black_king_white_knight_neither_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 3, 3).unwrap();
-        let piece2 = Piece::new(&"c", 1, 1).unwrap();
+        let piece1 = Piece::new("R", 3, 3).unwrap();
+        let piece2 = Piece::new("c", 1, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -168,8 +228,8 @@ mod tests {
     #[test]
     fn test_fight_black_rook_white_king_black_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"T", 3, 5).unwrap();
-        let piece2 = Piece::new(&"r", 3, 3).unwrap();
+        let piece1 = Piece::new("T", 3, 5).unwrap();
+        let piece2 = Piece::new("r", 3, 3).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -183,8 +243,8 @@ mod tests {
     #[test]
     fn test_fight_black_king_white_pawn_both_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 3, 3).unwrap();
-        let piece2 = Piece::new(&"p", 4, 2).unwrap();
+        let piece1 = Piece::new("R", 3, 3).unwrap();
+        let piece2 = Piece::new("p", 4, 2).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -198,8 +258,8 @@ mod tests {
     #[test]
     fn test_fight_black_queen_white_bishop_both_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"D", 5, 6).unwrap();
-        let piece2 = Piece::new(&"a", 1, 2).unwrap();
+        let piece1 = Piece::new("D", 5, 6).unwrap();
+        let piece2 = Piece::new("a", 1, 2).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -213,8 +273,8 @@ mod tests {
     #[test]
     fn test_fight_black_queen_white_knight_white_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"D", 5, 6).unwrap();
-        let piece2 = Piece::new(&"c", 4, 4).unwrap();
+        let piece1 = Piece::new("D", 5, 6).unwrap();
+        let piece2 = Piece::new("c", 4, 4).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -228,8 +288,8 @@ mod tests {
     #[test]
     fn test_fight_black_queen_white_rook_neither_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"D", 5, 6).unwrap();
-        let piece2 = Piece::new(&"t", 3, 0).unwrap();
+        let piece1 = Piece::new("D", 5, 6).unwrap();
+        let piece2 = Piece::new("t", 3, 0).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -243,8 +303,8 @@ mod tests {
     #[test]
     fn test_fight_black_queen_white_pawn_black_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"D", 5, 6).unwrap();
-        let piece2 = Piece::new(&"p", 3, 4).unwrap();
+        let piece1 = Piece::new("D", 5, 6).unwrap();
+        let piece2 = Piece::new("p", 3, 4).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -258,8 +318,8 @@ mod tests {
     #[test]
     fn test_fight_black_knight_white_bishop_white_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"a", 3, 3).unwrap();
-        let piece2 = Piece::new(&"C", 1, 1).unwrap();
+        let piece1 = Piece::new("a", 3, 3).unwrap();
+        let piece2 = Piece::new("C", 1, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -273,8 +333,8 @@ mod tests {
     #[test]
     fn test_fight_black_bishop_white_rook_white_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"A", 3, 3).unwrap();
-        let piece2 = Piece::new(&"t", 3, 0).unwrap();
+        let piece1 = Piece::new("A", 3, 3).unwrap();
+        let piece2 = Piece::new("t", 3, 0).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -288,8 +348,8 @@ mod tests {
     #[test]
     fn test_fight_black_bishop_white_pawn_both_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"A", 3, 3).unwrap();
-        let piece2 = Piece::new(&"p", 2, 2).unwrap();
+        let piece1 = Piece::new("A", 3, 3).unwrap();
+        let piece2 = Piece::new("p", 2, 2).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -303,8 +363,8 @@ mod tests {
     #[test]
     fn test_fight_black_knight_white_rook_neither_win() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"C", 0, 0).unwrap();
-        let piece2 = Piece::new(&"t", 7, 7).unwrap();
+        let piece1 = Piece::new("C", 0, 0).unwrap();
+        let piece2 = Piece::new("t", 7, 7).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -318,8 +378,8 @@ mod tests {
     #[test]
     fn test_fight_black_pawn_white_knight_black_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"c", 0, 0).unwrap();
-        let piece2 = Piece::new(&"P", 1, 1).unwrap();
+        let piece1 = Piece::new("c", 0, 0).unwrap();
+        let piece2 = Piece::new("P", 1, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -333,8 +393,8 @@ mod tests {
     #[test]
     fn test_fight_black_pawn_white_rook_white_wins() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"t", 1, 2).unwrap();
-        let piece2 = Piece::new(&"P", 1, 1).unwrap();
+        let piece1 = Piece::new("t", 1, 2).unwrap();
+        let piece2 = Piece::new("P", 1, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -1,11 +1,5 @@
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
-// Piece Types
-use crate::piece_type_mod::bishop_type::bishop::Bishop;
-use crate::piece_type_mod::king_type::king::King;
-use crate::piece_type_mod::knight_type::knight::Knight;
-use crate::piece_type_mod::pawn_type::pawn::Pawn;
-use crate::piece_type_mod::queen_type::queen::Queen;
-use crate::piece_type_mod::rook_type::rook::Rook;
 // Other
 use crate::color::Color;
 use crate::piece_movement::movement::Movement;
@@ -30,6 +24,15 @@ impl Piece {
         })
     }
 
+    /// Creates a Piece of the given kind and color in the given position.
+    pub fn from_kind(kind: PieceKind, color: Color, position: Position) -> Piece {
+        Piece {
+            color,
+            position,
+            piece_type: kind.piece_type(),
+        }
+    }
+
     /// Prints piece info. This is used for testing.
     pub fn print_info(&self) {
         println!("{}", self.get_info());
@@ -38,10 +41,10 @@ impl Piece {
     /// Formats the piece's info.
     pub fn get_info(&self) -> String {
         format!(
-            "Type: {},Color: {:?}, Posicion: {:?}",
+            "Type: {},Color: {:?}, Posicion: {}",
             self.piece_type.string(),
             self.color,
-            self.get_position().get_pair()
+            self.get_position()
         )
     }
 
@@ -60,26 +63,27 @@ impl Piece {
     }
 
     /// Type getter
-    pub fn get_type(&self) -> &Box<dyn PieceType> {
-        &self.piece_type
+    pub fn get_type(&self) -> &dyn PieceType {
+        self.piece_type.as_ref()
     }
 
     /// Parses the piece's name into a type for behavior.
     fn get_piece_type(name: &str) -> Option<Box<dyn PieceType>> {
         let name_up = name.to_uppercase();
-        match &name_up[..] {
-            "R" => Some(Box::new(King {})),
-            "D" => Some(Box::new(Queen {})),
-            "A" => Some(Box::new(Bishop {})),
-            "C" => Some(Box::new(Knight {})),
-            "T" => Some(Box::new(Rook {})),
-            "P" => Some(Box::new(Pawn {})),
-            _ => None,
-        }
+        let kind = match &name_up[..] {
+            "R" => PieceKind::King,
+            "D" => PieceKind::Queen,
+            "A" => PieceKind::Bishop,
+            "C" => PieceKind::Knight,
+            "T" => PieceKind::Rook,
+            "P" => PieceKind::Pawn,
+            _ => return None,
+        };
+        Some(kind.piece_type())
     }
 
     /// Returns true if the piece can capture the other_piece. Otherwise it returns false.
-    pub fn can_capture(&self, other_piece: &Piece) -> Movement {
+    pub fn can_capture(&self, other_piece: &Piece) -> Movement<'_> {
         let capture = &self.piece_type.can_capture(self, &other_piece.position);
 
         Movement {
@@ -89,116 +93,157 @@ impl Piece {
     }
 }
 
+/// Serializable form of a Piece: the piece type is stored as a PieceKind instead of a trait object.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PieceRecord {
+    piece_type: PieceKind,
+    color: Color,
+    position: Position,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Piece {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PieceRecord {
+            piece_type: self.piece_type.kind(),
+            color: self.color,
+            position: self.position,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Piece {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = PieceRecord::deserialize(deserializer)?;
+        Ok(Piece::from_kind(
+            record.piece_type,
+            record.color,
+            record.position,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_new_black_king_piece() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let info_expected = "Type: King,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let info_expected = "Type: King,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_king_piece() {
-        let piece = Piece::new(&"r", 3, 3).unwrap();
-        let info_expected = "Type: King,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("r", 3, 3).unwrap();
+        let info_expected = "Type: King,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_black_queen_piece() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let info_expected = "Type: Queen,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let info_expected = "Type: Queen,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_queen_piece() {
-        let piece = Piece::new(&"d", 3, 3).unwrap();
-        let info_expected = "Type: Queen,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("d", 3, 3).unwrap();
+        let info_expected = "Type: Queen,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_black_bishop_piece() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let info_expected = "Type: Bishop,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let info_expected = "Type: Bishop,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_bishop_piece() {
-        let piece = Piece::new(&"a", 3, 3).unwrap();
-        let info_expected = "Type: Bishop,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("a", 3, 3).unwrap();
+        let info_expected = "Type: Bishop,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_black_knight_piece() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let info_expected = "Type: Knight,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let info_expected = "Type: Knight,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_knight_piece() {
-        let piece = Piece::new(&"c", 3, 3).unwrap();
-        let info_expected = "Type: Knight,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("c", 3, 3).unwrap();
+        let info_expected = "Type: Knight,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_black_rook_piece() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let info_expected = "Type: Rook,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let info_expected = "Type: Rook,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_rook_piece() {
-        let piece = Piece::new(&"t", 3, 3).unwrap();
-        let info_expected = "Type: Rook,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("t", 3, 3).unwrap();
+        let info_expected = "Type: Rook,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_black_pawn_piece() {
-        let piece = Piece::new(&"P", 3, 3).unwrap();
-        let info_expected = "Type: Pawn,Color: Black, Posicion: [3, 3]";
+        let piece = Piece::new("P", 3, 3).unwrap();
+        let info_expected = "Type: Pawn,Color: Black, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_pawn_piece() {
-        let piece = Piece::new(&"p", 3, 3).unwrap();
-        let info_expected = "Type: Pawn,Color: White, Posicion: [3, 3]";
+        let piece = Piece::new("p", 3, 3).unwrap();
+        let info_expected = "Type: Pawn,Color: White, Posicion: d4";
 
         assert_eq!(piece.get_info(), info_expected);
     }
 
     #[test]
     fn test_new_invalid_piece() {
-        let piece = Piece::new(&"X", 3, 3);
+        let piece = Piece::new("X", 3, 3);
 
         assert!(piece.is_none());
     }
 
+    #[test]
+    fn test_from_kind() {
+        let piece = Piece::from_kind(PieceKind::Rook, Color::White, Position { x: 3, y: 3 });
+        let info_expected = "Type: Rook,Color: White, Posicion: d4";
+
+        assert_eq!(piece.get_info(), info_expected);
+    }
+
     #[test]
     fn test_get_position() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
         let position_expected = Position { x: 3, y: 3 };
 
         assert_eq!(piece.get_position(), &position_expected);
@@ -206,14 +251,14 @@ mod tests {
 
     #[test]
     fn test_get_color_black() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
 
         assert_eq!(piece.color, Color::Black);
     }
 
     #[test]
     fn test_get_color_white() {
-        let piece = Piece::new(&"r", 3, 3).unwrap();
+        let piece = Piece::new("r", 3, 3).unwrap();
 
         assert_eq!(piece.color, Color::White);
     }
@@ -221,72 +266,72 @@ mod tests {
     // King
     #[test]
     fn test_king_captures_pos_1() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 2, 4).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 2, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_2() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 3, 4).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 3, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_3() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 4, 4).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 4, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_4() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 4, 3).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 4, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_5() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 4, 2).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 4, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_6() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 3, 2).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 3, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_7() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 2, 2).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 2, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_captures_pos_8() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 2, 3).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 2, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_king_doesnt_capture() {
-        let piece = Piece::new(&"R", 3, 3).unwrap();
-        let other_piece = Piece::new(&"r", 5, 5).unwrap();
+        let piece = Piece::new("R", 3, 3).unwrap();
+        let other_piece = Piece::new("r", 5, 5).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -294,32 +339,32 @@ mod tests {
     // Queen
     #[test]
     fn test_queen_captures_pos_1() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 1, 5).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 1, 5).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_2() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 3, 5).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 3, 5).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_3() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 5).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 5).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_4() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 3).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
@@ -327,40 +372,40 @@ mod tests {
     #[test]
 
     fn test_queen_captures_pos_5() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 1).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 1).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_6() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 3, 1).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 3, 1).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_7() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 1, 1).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 1, 1).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_captures_pos_8() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 1, 3).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 1, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_queen_doesnt_capture() {
-        let piece = Piece::new(&"D", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 7).unwrap();
+        let piece = Piece::new("D", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -368,40 +413,40 @@ mod tests {
     // Bishop
     #[test]
     fn test_bishop_captures_pos_1() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 0, 6).unwrap();
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 0, 6).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_bishop_captures_pos_2() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 6, 6).unwrap();
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 6, 6).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_bishop_captures_pos_3() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 6, 0).unwrap();
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 6, 0).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_bishop_captures_pos_4() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 0, 0).unwrap();
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 0, 0).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_bishop_doesnt_capture() {
-        let piece = Piece::new(&"A", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 7).unwrap();
+        let piece = Piece::new("A", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -409,72 +454,72 @@ mod tests {
     // Knight
     #[test]
     fn test_knight_captures_pos_1() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 1, 2).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 1, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_2() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 1, 4).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 1, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_3() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 2, 1).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 2, 1).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_4() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 2, 5).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 2, 5).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_5() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 4, 1).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 4, 1).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_6() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 4, 5).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 4, 5).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_7() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 2).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_captures_pos_8() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 4).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_knight_doesnt_capture() {
-        let piece = Piece::new(&"C", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 7).unwrap();
+        let piece = Piece::new("C", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -482,40 +527,40 @@ mod tests {
     // Rook
     #[test]
     fn test_rook_captures_pos_1() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 3, 0).unwrap();
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 3, 0).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_rook_captures_pos_2() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 3, 7).unwrap();
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 3, 7).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_rook_captures_pos_3() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 0, 3).unwrap();
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 0, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_rook_captures_pos_4() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 7, 3).unwrap();
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 7, 3).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_rook_doesnt_capture() {
-        let piece = Piece::new(&"T", 3, 3).unwrap();
-        let other_piece = Piece::new(&"p", 5, 7).unwrap();
+        let piece = Piece::new("T", 3, 3).unwrap();
+        let other_piece = Piece::new("p", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -523,24 +568,24 @@ mod tests {
     // Black Pawn
     #[test]
     fn test_black_pawn_captures_pos_1() {
-        let piece = Piece::new(&"P", 3, 3).unwrap();
-        let other_piece = Piece::new(&"t", 2, 2).unwrap();
+        let piece = Piece::new("P", 3, 3).unwrap();
+        let other_piece = Piece::new("t", 2, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_black_pawn_captures_pos_2() {
-        let piece = Piece::new(&"P", 3, 3).unwrap();
-        let other_piece = Piece::new(&"t", 4, 2).unwrap();
+        let piece = Piece::new("P", 3, 3).unwrap();
+        let other_piece = Piece::new("t", 4, 2).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_black_pawn_doesnt_capture() {
-        let piece = Piece::new(&"P", 3, 3).unwrap();
-        let other_piece = Piece::new(&"t", 5, 7).unwrap();
+        let piece = Piece::new("P", 3, 3).unwrap();
+        let other_piece = Piece::new("t", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -548,24 +593,24 @@ mod tests {
     // White Pawn
     #[test]
     fn test_white_pawn_captures_pos_1() {
-        let piece = Piece::new(&"p", 3, 3).unwrap();
-        let other_piece = Piece::new(&"T", 2, 4).unwrap();
+        let piece = Piece::new("p", 3, 3).unwrap();
+        let other_piece = Piece::new("T", 2, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_white_pawn_captures_pos_2() {
-        let piece = Piece::new(&"p", 3, 3).unwrap();
-        let other_piece = Piece::new(&"T", 4, 4).unwrap();
+        let piece = Piece::new("p", 3, 3).unwrap();
+        let other_piece = Piece::new("T", 4, 4).unwrap();
 
         assert!(piece.can_capture(&other_piece).did_win);
     }
 
     #[test]
     fn test_white_pawn_doesnt_capture() {
-        let piece = Piece::new(&"p", 3, 3).unwrap();
-        let other_piece = Piece::new(&"T", 5, 7).unwrap();
+        let piece = Piece::new("p", 3, 3).unwrap();
+        let other_piece = Piece::new("T", 5, 7).unwrap();
 
         assert!(!piece.can_capture(&other_piece).did_win);
     }
@@ -1,4 +1,5 @@
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
 use crate::position_mod::position::Position;
 
@@ -15,6 +16,16 @@ impl PieceType for Bishop {
         false
     }
 
+    /// Returns the piece's kind
+    fn kind(&self) -> PieceKind {
+        PieceKind::Bishop
+    }
+
+    /// Returns the piece's material value
+    fn value(&self) -> i32 {
+        3
+    }
+
     /// Returns true if the piece can capture in the given position.
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool {
         let mut result = false;
@@ -39,7 +50,19 @@ mod tests {
     #[test]
     fn test_is_king() {
         let bishop = Bishop {};
-        assert_eq!(bishop.is_king(), false);
+        assert!(!bishop.is_king());
+    }
+
+    #[test]
+    fn test_kind() {
+        let bishop = Bishop {};
+        assert_eq!(bishop.kind(), PieceKind::Bishop);
+    }
+
+    #[test]
+    fn test_value() {
+        let bishop = Bishop {};
+        assert_eq!(bishop.value(), 3);
     }
 
     // can_capture coverage is assumed to be included in piece_type tests
@@ -1,4 +1,5 @@
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
 use crate::position_mod::position::Position;
 
@@ -15,6 +16,16 @@ impl PieceType for King {
         true
     }
 
+    /// Returns the piece's kind
+    fn kind(&self) -> PieceKind {
+        PieceKind::King
+    }
+
+    /// Returns the piece's material value
+    fn value(&self) -> i32 {
+        100
+    }
+
     /// Returns true if the piece can capture in the given position.
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool {
         let mut result = false;
@@ -41,7 +52,19 @@ mod tests {
     #[test]
     fn test_is_king() {
         let king = King {};
-        assert_eq!(king.is_king(), true);
+        assert!(king.is_king());
+    }
+
+    #[test]
+    fn test_kind() {
+        let king = King {};
+        assert_eq!(king.kind(), PieceKind::King);
+    }
+
+    #[test]
+    fn test_value() {
+        let king = King {};
+        assert_eq!(king.value(), 100);
     }
 
     // can_capture coverage is assumed to be included in piece_type tests
@@ -1,4 +1,5 @@
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
 use crate::position_mod::position::Position;
 
@@ -15,6 +16,16 @@ impl PieceType for Knight {
         false
     }
 
+    /// Returns the piece's kind
+    fn kind(&self) -> PieceKind {
+        PieceKind::Knight
+    }
+
+    /// Returns the piece's material value
+    fn value(&self) -> i32 {
+        3
+    }
+
     /// Returns true if the piece can capture in the given position.
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool {
         let mut result = false;
@@ -47,7 +58,19 @@ mod tests {
     #[test]
     fn test_is_king() {
         let knight = Knight {};
-        assert_eq!(knight.is_king(), false);
+        assert!(!knight.is_king());
+    }
+
+    #[test]
+    fn test_kind() {
+        let knight = Knight {};
+        assert_eq!(knight.kind(), PieceKind::Knight);
+    }
+
+    #[test]
+    fn test_value() {
+        let knight = Knight {};
+        assert_eq!(knight.value(), 3);
     }
 
     // can_capture coverage is assumed to be included in piece_type tests
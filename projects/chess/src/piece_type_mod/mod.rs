@@ -2,6 +2,7 @@ pub mod bishop_type;
 pub mod king_type;
 pub mod knight_type;
 pub mod pawn_type;
+pub mod piece_kind;
 pub mod piece_type;
 pub mod queen_type;
 pub mod rook_type;
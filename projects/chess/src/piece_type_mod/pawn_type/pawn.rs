@@ -1,5 +1,6 @@
 use crate::color::Color;
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
 use crate::position_mod::position::Position;
 
@@ -16,6 +17,16 @@ impl PieceType for Pawn {
         false
     }
     
+    /// Returns the piece's kind
+    fn kind(&self) -> PieceKind {
+        PieceKind::Pawn
+    }
+
+    /// Returns the piece's material value
+    fn value(&self) -> i32 {
+        1
+    }
+
     /// Returns true if the piece can capture in the given position.
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool {
         let result = false;
@@ -49,7 +60,19 @@ mod tests {
     #[test]
     fn test_is_king() {
         let pawn = Pawn {};
-        assert_eq!(pawn.is_king(), false);
+        assert!(!pawn.is_king());
+    }
+
+    #[test]
+    fn test_kind() {
+        let pawn = Pawn {};
+        assert_eq!(pawn.kind(), PieceKind::Pawn);
+    }
+
+    #[test]
+    fn test_value() {
+        let pawn = Pawn {};
+        assert_eq!(pawn.value(), 1);
     }
 
     // can_capture coverage is assumed to be included in piece_type tests
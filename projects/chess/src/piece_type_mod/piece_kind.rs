@@ -0,0 +1,127 @@
+use crate::color::Color;
+use crate::piece_type_mod::bishop_type::bishop::Bishop;
+use crate::piece_type_mod::king_type::king::King;
+use crate::piece_type_mod::knight_type::knight::Knight;
+use crate::piece_type_mod::pawn_type::pawn::Pawn;
+use crate::piece_type_mod::piece_type::PieceType;
+use crate::piece_type_mod::queen_type::queen::Queen;
+use crate::piece_type_mod::rook_type::rook::Rook;
+
+/// Names every Piece Type. Unlike the PieceType trait objects, it can be copied, compared and stored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceKind {
+    King,
+    Queen,
+    Bishop,
+    Knight,
+    Rook,
+    Pawn,
+}
+
+impl PieceKind {
+    /// Every kind, from the most to the least valuable.
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::King,
+        PieceKind::Queen,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Knight,
+        PieceKind::Pawn,
+    ];
+
+    /// Returns the Piece Type that gives this kind its behavior.
+    pub fn piece_type(&self) -> Box<dyn PieceType> {
+        match self {
+            PieceKind::King => Box::new(King {}),
+            PieceKind::Queen => Box::new(Queen {}),
+            PieceKind::Bishop => Box::new(Bishop {}),
+            PieceKind::Knight => Box::new(Knight {}),
+            PieceKind::Rook => Box::new(Rook {}),
+            PieceKind::Pawn => Box::new(Pawn {}),
+        }
+    }
+
+    /// Returns the letter used in the table format: uppercase for black, lowercase for white.
+    pub fn table_name(&self, color: Color) -> String {
+        let name = match self {
+            PieceKind::King => "R",
+            PieceKind::Queen => "D",
+            PieceKind::Bishop => "A",
+            PieceKind::Knight => "C",
+            PieceKind::Rook => "T",
+            PieceKind::Pawn => "P",
+        };
+        match color {
+            Color::Black => name.to_string(),
+            Color::White => name.to_lowercase(),
+        }
+    }
+
+    /// Returns the letter used in FEN: English initials, uppercase for white and lowercase for black.
+    pub fn fen_char(&self, color: Color) -> char {
+        let name = match self {
+            PieceKind::King => 'K',
+            PieceKind::Queen => 'Q',
+            PieceKind::Bishop => 'B',
+            PieceKind::Knight => 'N',
+            PieceKind::Rook => 'R',
+            PieceKind::Pawn => 'P',
+        };
+        match color {
+            Color::White => name,
+            Color::Black => name.to_ascii_lowercase(),
+        }
+    }
+
+    /// Returns the kind and color named by a FEN letter. None if the letter is unknown.
+    pub fn from_fen_char(letter: char) -> Option<(PieceKind, Color)> {
+        let kind = match letter.to_ascii_uppercase() {
+            'K' => PieceKind::King,
+            'Q' => PieceKind::Queen,
+            'B' => PieceKind::Bishop,
+            'N' => PieceKind::Knight,
+            'R' => PieceKind::Rook,
+            'P' => PieceKind::Pawn,
+            _ => return None,
+        };
+        let color = if letter.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        Some((kind, color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_type() {
+        for kind in PieceKind::ALL {
+            assert_eq!(kind.piece_type().kind(), kind);
+        }
+    }
+
+    #[test]
+    fn test_table_name() {
+        assert_eq!(PieceKind::Knight.table_name(Color::Black), "C");
+        assert_eq!(PieceKind::Knight.table_name(Color::White), "c");
+    }
+
+    #[test]
+    fn test_fen_char() {
+        for kind in PieceKind::ALL {
+            for color in [Color::White, Color::Black] {
+                assert_eq!(
+                    PieceKind::from_fen_char(kind.fen_char(color)),
+                    Some((kind, color))
+                );
+            }
+        }
+        assert_eq!(PieceKind::Knight.fen_char(Color::White), 'N');
+        assert_eq!(PieceKind::from_fen_char('x'), None);
+    }
+}
@@ -1,12 +1,16 @@
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::position_mod::position::Position;
 use std::ops::Range;
 
 /// Represents a Piece Type. This is what gives a piece its behavior.
-pub trait PieceType {
+/// Piece Types hold no state, so Pieces and Boards can be sent to and shared between threads.
+pub trait PieceType: Send + Sync {
     fn string(&self) -> &str;
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool;
     fn is_king(&self) -> bool;
+    fn kind(&self) -> PieceKind;
+    fn value(&self) -> i32;
 
     // ******* Diagonal squares
     /// It returns true if the piece can capture in the given position in the diagonal squares from the main piece.
@@ -91,7 +95,6 @@ pub trait PieceType {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::piece_type_mod::bishop_type::bishop::Bishop;
     use crate::piece_type_mod::king_type::king::King;
     use crate::piece_type_mod::knight_type::knight::Knight;
@@ -1,4 +1,5 @@
 use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
 use crate::piece_type_mod::piece_type::PieceType;
 use crate::position_mod::position::Position;
 
@@ -15,6 +16,16 @@ impl PieceType for Queen {
         false
     }
 
+    /// Returns the piece's kind
+    fn kind(&self) -> PieceKind {
+        PieceKind::Queen
+    }
+
+    /// Returns the piece's material value
+    fn value(&self) -> i32 {
+        9
+    }
+
     /// Returns true if the piece can capture in the given position.
     fn can_capture(&self, main_piece: &Piece, p2: &Position) -> bool {
         let mut result = false;
@@ -42,7 +53,19 @@ mod tests {
     #[test]
     fn test_is_king() {
         let queen = Queen {};
-        assert_eq!(queen.is_king(), false);
+        assert!(!queen.is_king());
+    }
+
+    #[test]
+    fn test_kind() {
+        let queen = Queen {};
+        assert_eq!(queen.kind(), PieceKind::Queen);
+    }
+
+    #[test]
+    fn test_value() {
+        let queen = Queen {};
+        assert_eq!(queen.value(), 9);
     }
 
     // can_capture coverage is assumed to be included in piece_type tests
@@ -1,15 +1,123 @@
-#[derive(Debug, PartialEq)]
+use crate::errors::chess_error::ChessError;
+use std::fmt;
+use std::str::FromStr;
+
+const FILES: &str = "abcdefgh";
+const RANKS: &str = "12345678";
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a position in the table. Axis are thought as in x-y: lower left corner is (0,0).
+/// In algebraic notation, x is the file (a-h) and y is the rank (1-8): (0,0) is a1 and (4,3) is e4.
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
 impl Position {
+    /// Tries to create a Position inside the 8x8 table. If it is outside, it returns None.
+    pub fn new(x: i32, y: i32) -> Option<Position> {
+        let position = Position { x, y };
+        if position.is_inside_table() {
+            Some(position)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the position is inside the 8x8 table.
+    pub fn is_inside_table(&self) -> bool {
+        (0..8).contains(&self.x) && (0..8).contains(&self.y)
+    }
+
+    /// Tries to create a Position from algebraic notation, like "e4". If it is not valid, it returns None.
+    pub fn from_algebraic(square: &str) -> Option<Position> {
+        let mut chars = square.chars();
+        let (file, rank) = (chars.next()?, chars.next()?);
+        if chars.next().is_some() {
+            return None;
+        }
+
+        let x = FILES.find(file.to_ascii_lowercase())?;
+        let y = RANKS.find(rank)?;
+        Position::new(x as i32, y as i32)
+    }
+
+    /// Returns the position in algebraic notation, like "e4". Positions outside the table show their raw pair.
+    pub fn to_algebraic(&self) -> String {
+        if !self.is_inside_table() {
+            return format!("{:?}", self.get_pair());
+        }
+        let file = FILES.as_bytes()[self.x as usize] as char;
+        let rank = RANKS.as_bytes()[self.y as usize] as char;
+        format!("{}{}", file, rank)
+    }
+
+    /// Returns the position moved by the given offset. If it falls outside the table, it returns None.
+    pub fn offset(&self, dx: i32, dy: i32) -> Option<Position> {
+        Position::new(self.x + dx, self.y + dy)
+    }
+
+    /// Returns the number of king moves needed to go from this position to the other one.
+    pub fn distance(&self, other: &Position) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Returns the number of rook steps (files plus ranks) between this position and the other one.
+    pub fn manhattan_distance(&self, other: &Position) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Returns the number of the square, from 0 (a1) to 63 (h8), going through each rank from file a to h.
+    pub fn index(&self) -> usize {
+        (self.y * 8 + self.x) as usize
+    }
+
+    /// Returns the Position of the square with the given number, from 0 (a1) to 63 (h8).
+    pub fn from_index(index: usize) -> Position {
+        Position {
+            x: (index % 8) as i32,
+            y: (index / 8) as i32,
+        }
+    }
+
     /// Returns the [x,y] pair
     pub fn get_pair(&self) -> [i32; 2] {
         [self.x, self.y]
     }
+
+    /// Returns the squares strictly between this position and the other one, when both share a
+    /// row, a column or a diagonal. Otherwise there is no path and the result is empty.
+    pub fn squares_between(&self, other: &Position) -> Vec<Position> {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        let aligned = dx == 0 || dy == 0 || dx.abs() == dy.abs();
+        if !aligned {
+            return Vec::new();
+        }
+
+        let (step_x, step_y) = (dx.signum(), dy.signum());
+        let steps = dx.abs().max(dy.abs());
+        (1..steps)
+            .map(|i| Position {
+                x: self.x + i * step_x,
+                y: self.y + i * step_y,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_algebraic())
+    }
+}
+
+impl FromStr for Position {
+    type Err = ChessError<'static>;
+
+    fn from_str(square: &str) -> Result<Self, Self::Err> {
+        Position::from_algebraic(square.trim()).ok_or(ChessError::SQUARE_NOTATION)
+    }
 }
 
 #[cfg(test)]
@@ -23,9 +131,110 @@ mod tests {
         assert_eq!(position.y, 2);
     }
 
+    #[test]
+    fn test_new_position_constructor() {
+        assert_eq!(Position::new(0, 7), Some(Position { x: 0, y: 7 }));
+    }
+
+    #[test]
+    fn test_new_position_outside_table() {
+        assert!(Position::new(8, 0).is_none());
+        assert!(Position::new(0, -1).is_none());
+    }
+
+    #[test]
+    fn test_from_algebraic() {
+        assert_eq!(
+            Position::from_algebraic("a1"),
+            Some(Position { x: 0, y: 0 })
+        );
+        assert_eq!(
+            Position::from_algebraic("e4"),
+            Some(Position { x: 4, y: 3 })
+        );
+        assert_eq!(
+            Position::from_algebraic("H8"),
+            Some(Position { x: 7, y: 7 })
+        );
+    }
+
+    #[test]
+    fn test_from_algebraic_invalid() {
+        assert!(Position::from_algebraic("i1").is_none());
+        assert!(Position::from_algebraic("a9").is_none());
+        assert!(Position::from_algebraic("a0").is_none());
+        assert!(Position::from_algebraic("e44").is_none());
+        assert!(Position::from_algebraic("").is_none());
+    }
+
+    #[test]
+    fn test_to_algebraic() {
+        assert_eq!(Position { x: 3, y: 3 }.to_algebraic(), "d4");
+        assert_eq!(Position { x: 7, y: 0 }.to_string(), "h1");
+        assert_eq!(Position { x: 8, y: 0 }.to_string(), "[8, 0]");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("c7".parse::<Position>(), Ok(Position { x: 2, y: 6 }));
+        assert_eq!("z7".parse::<Position>(), Err(ChessError::SQUARE_NOTATION));
+    }
+
+    #[test]
+    fn test_offset() {
+        let e4 = Position { x: 4, y: 3 };
+
+        assert_eq!(e4.offset(1, 2), Position::from_algebraic("f6"));
+        assert!(e4.offset(4, 0).is_none());
+    }
+
+    #[test]
+    fn test_distances() {
+        let a1 = Position { x: 0, y: 0 };
+        let c4 = Position { x: 2, y: 3 };
+
+        assert_eq!(a1.distance(&c4), 3);
+        assert_eq!(a1.manhattan_distance(&c4), 5);
+    }
+
     #[test]
     fn test_get_pair() {
         let position = Position { x: 1, y: 2 };
         assert_eq!(position.get_pair(), [1, 2]);
     }
+
+    #[test]
+    fn test_squares_between_diagonal() {
+        let from = Position { x: 0, y: 0 };
+        let to = Position { x: 3, y: 3 };
+        let expected = vec![Position { x: 1, y: 1 }, Position { x: 2, y: 2 }];
+
+        assert_eq!(from.squares_between(&to), expected);
+    }
+
+    #[test]
+    fn test_squares_between_column() {
+        let from = Position { x: 4, y: 6 };
+        let to = Position { x: 4, y: 3 };
+        let expected = vec![Position { x: 4, y: 5 }, Position { x: 4, y: 4 }];
+
+        assert_eq!(from.squares_between(&to), expected);
+    }
+
+    #[test]
+    fn test_squares_between_not_aligned() {
+        let from = Position { x: 0, y: 0 };
+        let to = Position { x: 1, y: 2 };
+
+        assert!(from.squares_between(&to).is_empty());
+    }
+
+    #[test]
+    fn test_index() {
+        let e4 = Position { x: 4, y: 3 };
+
+        assert_eq!(e4.index(), 28);
+        assert_eq!(Position::from_index(28), e4);
+        assert_eq!(Position::from_index(63), Position { x: 7, y: 7 });
+    }
 }
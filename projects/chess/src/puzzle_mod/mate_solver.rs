@@ -0,0 +1,282 @@
+use crate::board_mod::board::Board;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+
+/// Represents a move of the attacking side that forces mate, with every defense and the way each one
+/// is answered. A move without defenses gives mate.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MateLine {
+    pub chess_move: Move,
+    pub san: String,
+    pub defenses: Vec<Defense>,
+}
+
+/// Represents a legal reply of the defending side and the attacking move that answers it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Defense {
+    pub chess_move: Move,
+    pub san: String,
+    pub answer: MateLine,
+}
+
+/// Represents a puzzle: a position where the side to move should mate in a number of moves.
+pub struct Puzzle {
+    pub board: Board,
+    pub moves: u32,
+}
+
+impl MateLine {
+    /// Returns the number of moves of the attacking side needed to mate against the best defense.
+    pub fn length(&self) -> u32 {
+        1 + self
+            .defenses
+            .iter()
+            .map(|d| d.answer.length())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the main line: the defending side always picks the reply that resists the longest.
+    pub fn main_line(&self) -> Vec<&str> {
+        let mut line = vec![self.san.as_str()];
+        if let Some(defense) = self.defenses.first() {
+            line.push(defense.san.as_str());
+            line.extend(defense.answer.main_line());
+        }
+        line
+    }
+}
+
+impl Puzzle {
+    /// Reads a puzzle written as a FEN line followed by a line with the number of moves to mate.
+    /// Empty lines and lines starting with '#' are skipped.
+    pub fn parse(text: &str) -> Result<Puzzle, ChessError<'static>> {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let (fen, moves) = match (lines.next(), lines.next(), lines.next()) {
+            (Some(fen), Some(moves), None) => (fen, moves),
+            _ => return Err(ChessError::PUZZLE),
+        };
+        let moves = moves
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or(ChessError::PUZZLE)?;
+        Ok(Puzzle {
+            board: Board::from_fen(fen)?,
+            moves,
+        })
+    }
+
+    /// Returns the fastest forced mate for the side to move, or None if there is no mate in the
+    /// number of moves of the puzzle.
+    pub fn solve(&self) -> Option<MateLine> {
+        solve_mate(&Mailbox::from_board(&self.board), self.moves)
+    }
+}
+
+/// Returns the fastest forced mate for the side to move in at most `moves` of its moves,
+/// or None if the rival can always avoid it.
+pub fn solve_mate(position: &Mailbox, moves: u32) -> Option<MateLine> {
+    (1..=moves).find_map(|n| mate_in(position, n))
+}
+
+/// Returns a mate in exactly `moves` moves, answering each defense with its fastest mate.
+fn mate_in(position: &Mailbox, moves: u32) -> Option<MateLine> {
+    for chess_move in attacking_moves(position) {
+        let after = position.play(&chess_move);
+        let replies = after.legal_moves();
+        let san = position.to_san(&chess_move);
+        if replies.is_empty() {
+            if after.in_check(after.side_to_move) {
+                return Some(MateLine {
+                    chess_move,
+                    san,
+                    defenses: Vec::new(),
+                });
+            }
+            continue;
+        }
+        if moves == 1 {
+            continue;
+        }
+
+        let total = replies.len();
+        let mut defenses = Vec::new();
+        for reply in replies {
+            match solve_mate(&after.play(&reply), moves - 1) {
+                Some(answer) => defenses.push(Defense {
+                    chess_move: reply,
+                    san: after.to_san(&reply),
+                    answer,
+                }),
+                None => break,
+            }
+        }
+        if defenses.len() == total {
+            defenses.sort_by_key(|d| std::cmp::Reverse(d.answer.length()));
+            return Some(MateLine {
+                chess_move,
+                san,
+                defenses,
+            });
+        }
+    }
+    None
+}
+
+/// Returns the legal moves with checks first, as they are the most likely to force mate.
+fn attacking_moves(position: &Mailbox) -> Vec<Move> {
+    let rival = position.side_to_move.opposite();
+    let mut moves = position.legal_moves();
+    moves.sort_by_key(|m| !position.play(m).in_check(rival));
+    moves
+}
+
+/// Returns the solution as a tree: one line per move, the defenses indented under the move they
+/// answer and the main line first.
+pub fn format_tree(position: &Mailbox, line: &MateLine) -> Vec<String> {
+    let mut lines = Vec::new();
+    push_line(
+        &mut lines,
+        line,
+        position.fullmove_number,
+        position.side_to_move,
+        0,
+    );
+    lines
+}
+
+fn push_line(lines: &mut Vec<String>, line: &MateLine, number: u32, color: Color, indent: usize) {
+    let dots = match color {
+        Color::White => ".",
+        Color::Black => "...",
+    };
+    lines.push(format!(
+        "{}{}{} {}",
+        " ".repeat(indent),
+        number,
+        dots,
+        line.san
+    ));
+
+    let (reply_number, reply_dots, next_number) = match color {
+        Color::White => (number, "...", number + 1),
+        Color::Black => (number + 1, ".", number + 1),
+    };
+    for defense in &line.defenses {
+        lines.push(format!(
+            "{}{}{} {}",
+            " ".repeat(indent + 2),
+            reply_number,
+            reply_dots,
+            defense.san
+        ));
+        push_line(lines, &defense.answer, next_number, color, indent + 4);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablebase_mod::material::Material;
+    use crate::tablebase_mod::tablebase::{Tablebases, Wdl};
+
+    fn puzzle(text: &str) -> Puzzle {
+        Puzzle::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_mate_in_one() {
+        let puzzle = puzzle(include_str!("../../test/puzzle1.txt"));
+        let line = puzzle.solve().unwrap();
+
+        assert_eq!(line.main_line(), vec!["Ra8#"]);
+        assert_eq!(line.length(), 1);
+        assert!(line.defenses.is_empty());
+    }
+
+    #[test]
+    fn test_mate_in_two() {
+        let puzzle = puzzle(include_str!("../../test/puzzle2.txt"));
+        let line = puzzle.solve().unwrap();
+
+        assert_eq!(line.length(), 2);
+        assert_eq!(line.main_line().len(), 3);
+        assert!(line.main_line()[2].ends_with('#'));
+        let position = Mailbox::from_board(&puzzle.board);
+        assert_eq!(
+            line.defenses.len(),
+            position.play(&line.chess_move).legal_moves().len()
+        );
+    }
+
+    #[test]
+    fn test_mate_in_three_with_black() {
+        let puzzle = puzzle(include_str!("../../test/puzzle3.txt"));
+        let line = puzzle.solve().unwrap();
+
+        assert_eq!(line.length(), 3);
+        let tree = format_tree(&Mailbox::from_board(&puzzle.board), &line);
+        assert!(tree[0].starts_with("1... "));
+        assert!(tree[1].starts_with("  2. "));
+        assert!(tree[2].starts_with("    2... "));
+    }
+
+    #[test]
+    fn test_refuted() {
+        let puzzle = puzzle(include_str!("../../test/puzzle4.txt"));
+
+        assert_eq!(puzzle.solve(), None);
+        assert!(solve_mate(&Mailbox::from_board(&puzzle.board), puzzle.moves + 1).is_some());
+    }
+
+    #[test]
+    fn test_stalemate_is_not_mate() {
+        // Qg6 leaves Black without moves but not in check: a stalemate, not a mate.
+        let board = Board::from_fen("7k/8/5K2/8/8/8/8/6Q1 w - - 0 1").unwrap();
+        let line = solve_mate(&Mailbox::from_board(&board), 1).unwrap();
+
+        let after = Mailbox::from_board(&board).play(&line.chess_move);
+        assert!(after.is_checkmate());
+    }
+
+    #[test]
+    fn test_agrees_with_tablebase() {
+        let material: Material = "KRvK".parse().unwrap();
+        let mut tablebases = Tablebases::default();
+        tablebases.generate(&material);
+
+        for fen in [
+            "4k3/8/4K3/8/8/8/8/7R w - - 0 1",
+            "6k1/8/5K2/8/8/8/8/7R w - - 0 1",
+            "8/7k/5K2/8/4R3/8/8/8 w - - 0 1",
+            "8/4R3/8/8/8/8/4K3/6k1 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let probe = tablebases.probe(&board).unwrap();
+            assert_eq!(probe.wdl, Wdl::Win);
+            let moves = probe.dtm.unwrap().div_ceil(2);
+
+            let position = Mailbox::from_board(&board);
+            assert_eq!(solve_mate(&position, moves).unwrap().length(), moves);
+            assert!(solve_mate(&position, moves - 1).is_none());
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_puzzles() {
+        for text in [
+            "",
+            "8/8/8/8/8/8/8/8 w - - 0 1",
+            "7k/8/8/8/8/8/8/K7 w - - 0 1\n0",
+            "x\ny\nz",
+        ] {
+            assert!(Puzzle::parse(text).is_err());
+        }
+    }
+}
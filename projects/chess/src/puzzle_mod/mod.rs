@@ -0,0 +1 @@
+pub mod mate_solver;
@@ -1,5 +1,4 @@
 #[derive(Debug)]
-
 /// Represents the result of a fight between two pieces.
 /// Possible Values:
 /// - B: White wins
@@ -16,12 +15,39 @@ impl FightResult<'_> {
     pub const BOTH_WIN: Self = Self { output: "E" };
     pub const NEITHER_WIN: Self = Self { output: "P" };
 
+    /// Returns the result matching the given output. None if the output is unknown.
+    pub fn from_output(output: &str) -> Option<FightResult<'static>> {
+        match output {
+            "B" => Some(FightResult::WHITE_WINS),
+            "N" => Some(FightResult::BLACK_WINS),
+            "E" => Some(FightResult::BOTH_WIN),
+            "P" => Some(FightResult::NEITHER_WIN),
+            _ => None,
+        }
+    }
+
     /// Prints the result of the fight
     pub fn print(&self) {
         println!("{}", &self.output);
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for FightResult<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.output)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FightResult<'static> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let output = String::deserialize(deserializer)?;
+        FightResult::from_output(&output)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown fight result {}", output)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +71,10 @@ mod tests {
     fn test_neither_win() {
         assert_eq!(FightResult::NEITHER_WIN.output, "P");
     }
+
+    #[test]
+    fn test_from_output() {
+        assert_eq!(FightResult::from_output("E").unwrap().output, "E");
+        assert!(FightResult::from_output("X").is_none());
+    }
 }
@@ -0,0 +1,134 @@
+use crate::errors::chess_error::ChessError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Serializes the value (a Board, a Piece, a FightResult...) into JSON.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, ChessError<'static>> {
+    serde_json::to_string(value).map_err(|_| ChessError::SERIALIZATION)
+}
+
+/// Deserializes a value from JSON.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, ChessError<'static>> {
+    serde_json::from_str(json).map_err(|_| ChessError::SERIALIZATION)
+}
+
+/// Serializes the value into a compact binary format.
+pub fn to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, ChessError<'static>> {
+    bincode::serialize(value).map_err(|_| ChessError::SERIALIZATION)
+}
+
+/// Deserializes a value from the compact binary format.
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ChessError<'static>> {
+    bincode::deserialize(bytes).map_err(|_| ChessError::SERIALIZATION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+    use crate::color::Color;
+    use crate::piece_mod::piece::Piece;
+    use crate::position_mod::position::Position;
+    use crate::results::fight_result::FightResult;
+
+    fn board() -> Board {
+        Board::builder()
+            .place("R", Position { x: 3, y: 3 })
+            .place("d", Position { x: 3, y: 4 })
+            .build()
+            .unwrap()
+    }
+
+    fn infos(board: &Board) -> Vec<String> {
+        board.pieces().map(|p| p.get_info()).collect()
+    }
+
+    #[test]
+    fn test_piece_json() {
+        let piece = Piece::new("d", 3, 4).unwrap();
+
+        assert_eq!(
+            to_json(&piece).unwrap(),
+            r#"{"piece_type":"Queen","color":"White","position":{"x":3,"y":4}}"#
+        );
+    }
+
+    #[test]
+    fn test_board_json_round_trip() {
+        let board = board();
+        let json = to_json(&board).unwrap();
+        let restored: Board = from_json(&json).unwrap();
+
+        assert_eq!(infos(&restored), infos(&board));
+    }
+
+    #[test]
+    fn test_board_bytes_round_trip() {
+        let board = board();
+        let bytes = to_bytes(&board).unwrap();
+        let restored: Board = from_bytes(&bytes).unwrap();
+
+        assert_eq!(infos(&restored), infos(&board));
+    }
+
+    #[test]
+    fn test_board_state_round_trip() {
+        let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQq d6 2 12").unwrap();
+        let restored: Board = from_bytes(&to_bytes(&board).unwrap()).unwrap();
+
+        assert_eq!(restored.to_fen(), board.to_fen());
+    }
+
+    #[test]
+    fn test_board_without_state_is_accepted() {
+        let json = r#"{"pieces":[{"piece_type":"King","color":"Black","position":{"x":3,"y":3}}]}"#;
+        let board: Board = from_json(json).unwrap();
+
+        assert_eq!(board.side_to_move(), Color::White);
+        assert_eq!(board.fullmove_number(), 1);
+    }
+
+    #[test]
+    fn test_invalid_board_is_rejected() {
+        let json = r#"{"pieces":[
+            {"piece_type":"King","color":"Black","position":{"x":3,"y":3}},
+            {"piece_type":"Queen","color":"White","position":{"x":3,"y":3}}]}"#;
+
+        assert!(from_json::<Board>(json).is_err());
+    }
+
+    #[test]
+    fn test_unknown_piece_type_is_rejected() {
+        let json = r#"{"piece_type":"Dragon","color":"Black","position":{"x":3,"y":3}}"#;
+
+        assert_eq!(
+            from_json::<Piece>(json).err(),
+            Some(ChessError::SERIALIZATION)
+        );
+    }
+
+    #[test]
+    fn test_fight_result_round_trip() {
+        let result = board().fight().unwrap().output.to_string();
+        let json = to_json(&FightResult::from_output(&result).unwrap()).unwrap();
+        let bytes = to_bytes(&FightResult::BOTH_WIN).unwrap();
+
+        assert_eq!(json, r#""E""#);
+        assert_eq!(from_json::<FightResult>(&json).unwrap().output, "E");
+        assert_eq!(from_bytes::<FightResult>(&bytes).unwrap().output, "E");
+    }
+
+    #[test]
+    fn test_position_and_color_round_trip() {
+        let position = Position { x: 7, y: 0 };
+
+        assert_eq!(
+            from_bytes::<Position>(&to_bytes(&position).unwrap()).unwrap(),
+            position
+        );
+        assert_eq!(
+            from_json::<Color>(&to_json(&Color::Black).unwrap()).unwrap(),
+            Color::Black
+        );
+    }
+}
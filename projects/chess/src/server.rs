@@ -0,0 +1,26 @@
+//! Opens a TCP port given by argv and plays chess games between pairs of clients.
+//! The line protocol is documented in `network_mod::protocol`; any client that reads and
+//! writes lines, like `nc <host> <port>`, can play.
+
+use chess::network_mod::server::run_server;
+use std::env::args;
+use std::net::TcpListener;
+
+static SERVER_ARGS: usize = 2;
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+fn main() -> Result<(), ()> {
+    let argv = args().collect::<Vec<String>>();
+    if argv.len() != SERVER_ARGS {
+        println!("Invalid number of arguments");
+        let app_name = &argv[0];
+        println!("Usage:\n{:?} <port>", app_name);
+        return Err(());
+    }
+
+    let address = "0.0.0.0:".to_owned() + &argv[1];
+    let listener = TcpListener::bind(&address).map_err(|e| println!("{}", e))?;
+    println!("Listening on {:?}", address);
+    run_server(listener, START_FEN).map_err(|e| println!("{}", e))
+}
@@ -0,0 +1,189 @@
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum number of pieces, kings included, of the endings a tablebase can be built for.
+pub const MAX_PIECES: usize = 4;
+
+/// Represents the pieces of an ending, like KQvK: White has a king and a queen, Black only a king.
+/// Kings are always there, so only the other pieces of each side are kept. Pawns are not supported.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Material {
+    white: Vec<PieceKind>,
+    black: Vec<PieceKind>,
+}
+
+impl Material {
+    /// Returns the material of the pieces in the position.
+    /// It fails unless each side has exactly one king, or if there are pawns or too many pieces.
+    pub fn of(position: &Mailbox) -> Result<Material, ChessError<'static>> {
+        let mut white = Vec::new();
+        let mut black = Vec::new();
+        let mut kings = 0;
+        for (_, color, kind) in position.pieces() {
+            match (kind, color) {
+                (PieceKind::King, _) => kings += 1,
+                (_, Color::White) => white.push(kind),
+                (_, Color::Black) => black.push(kind),
+            }
+        }
+        let both_kings =
+            position.king(Color::White).is_some() && position.king(Color::Black).is_some();
+        if kings != 2 || !both_kings {
+            return Err(ChessError::TABLEBASE_MATERIAL);
+        }
+        Material::new(white, black)
+    }
+
+    /// Creates the material from the pieces of each side, kings left out.
+    fn new(
+        mut white: Vec<PieceKind>,
+        mut black: Vec<PieceKind>,
+    ) -> Result<Material, ChessError<'static>> {
+        let pieces = white.iter().chain(black.iter());
+        let unsupported = pieces
+            .clone()
+            .any(|k| *k == PieceKind::King || *k == PieceKind::Pawn);
+        if unsupported || pieces.count() + 2 > MAX_PIECES {
+            return Err(ChessError::TABLEBASE_MATERIAL);
+        }
+
+        let order = |k: &PieceKind| PieceKind::ALL.iter().position(|a| a == k);
+        white.sort_by_key(order);
+        black.sort_by_key(order);
+        Ok(Material { white, black })
+    }
+
+    /// Returns every piece, kings included, in the order they are indexed:
+    /// white king, other white pieces, black king, other black pieces.
+    pub fn pieces(&self) -> Vec<(Color, PieceKind)> {
+        let mut pieces = vec![(Color::White, PieceKind::King)];
+        pieces.extend(self.white.iter().map(|k| (Color::White, *k)));
+        pieces.push((Color::Black, PieceKind::King));
+        pieces.extend(self.black.iter().map(|k| (Color::Black, *k)));
+        pieces
+    }
+
+    /// Returns the same material with the colors swapped: KQvK becomes KvKQ.
+    pub fn swapped(&self) -> Material {
+        Material {
+            white: self.black.clone(),
+            black: self.white.clone(),
+        }
+    }
+
+    /// Returns true if only the kings are left, which is always a draw.
+    pub fn only_kings(&self) -> bool {
+        self.white.is_empty() && self.black.is_empty()
+    }
+
+    /// Returns the materials left after one piece, other than a king, is captured.
+    pub fn after_captures(&self) -> Vec<Material> {
+        let mut result = Vec::new();
+        for i in 0..self.white.len() {
+            let mut white = self.white.clone();
+            white.remove(i);
+            result.push(Material {
+                white,
+                black: self.black.clone(),
+            });
+        }
+        for i in 0..self.black.len() {
+            let mut black = self.black.clone();
+            black.remove(i);
+            result.push(Material {
+                white: self.white.clone(),
+                black,
+            });
+        }
+        result.dedup();
+        result
+    }
+}
+
+impl FromStr for Material {
+    type Err = ChessError<'static>;
+
+    /// Parses the material written as the pieces of White, a 'v' and the pieces of Black, like KRvK.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let (white, black) = name.split_once('v').ok_or(ChessError::TABLEBASE_MATERIAL)?;
+        let side = |letters: &str| -> Result<Vec<PieceKind>, ChessError<'static>> {
+            let rest = letters
+                .strip_prefix('K')
+                .ok_or(ChessError::TABLEBASE_MATERIAL)?;
+            rest.chars()
+                .map(|c| match PieceKind::from_fen_char(c) {
+                    Some((kind, Color::White)) => Ok(kind),
+                    _ => Err(ChessError::TABLEBASE_MATERIAL),
+                })
+                .collect()
+        };
+        Material::new(side(white)?, side(black)?)
+    }
+}
+
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters = |pieces: &[PieceKind]| -> String {
+            pieces.iter().map(|k| k.fen_char(Color::White)).collect()
+        };
+        write!(f, "K{}vK{}", letters(&self.white), letters(&self.black))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_mod::board::Board;
+
+    #[test]
+    fn test_from_str() {
+        let material: Material = "KNQvK".parse().unwrap();
+
+        assert_eq!(material.to_string(), "KQNvK");
+        assert_eq!(
+            material.pieces(),
+            vec![
+                (Color::White, PieceKind::King),
+                (Color::White, PieceKind::Queen),
+                (Color::White, PieceKind::Knight),
+                (Color::Black, PieceKind::King)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_material() {
+        for name in ["KPvK", "KQQQvK", "KQvKRR", "QvK", "KQK", "KKvK"] {
+            assert_eq!(
+                name.parse::<Material>().err(),
+                Some(ChessError::TABLEBASE_MATERIAL)
+            );
+        }
+    }
+
+    #[test]
+    fn test_of() {
+        let board = Board::from_fen("8/8/3k4/8/8/2R5/8/4K3 b - - 0 1").unwrap();
+        let material = Material::of(&Mailbox::from_board(&board)).unwrap();
+
+        assert_eq!(material.to_string(), "KRvK");
+        assert_eq!(material.swapped().to_string(), "KvKR");
+    }
+
+    #[test]
+    fn test_after_captures() {
+        let material: Material = "KQvKR".parse().unwrap();
+        let names: Vec<String> = material
+            .after_captures()
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+
+        assert_eq!(names, vec!["KvKR", "KQvK"]);
+        assert!("KvK".parse::<Material>().unwrap().only_kings());
+    }
+}
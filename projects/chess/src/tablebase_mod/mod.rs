@@ -0,0 +1,2 @@
+pub mod material;
+pub mod tablebase;
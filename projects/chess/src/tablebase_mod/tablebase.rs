@@ -0,0 +1,634 @@
+use crate::board_mod::board::Board;
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::move_gen_mod::chess_move::Move;
+use crate::move_gen_mod::mailbox::Mailbox;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+use crate::tablebase_mod::material::{Material, MAX_PIECES};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// First bytes of every tablebase file.
+const MAGIC: &[u8; 8] = b"CHESSTB1";
+
+/// Values stored for positions that are not distances to mate.
+const ILLEGAL: u8 = 255;
+const DRAW: u8 = 254;
+const UNKNOWN: u8 = 253;
+
+/// Represents the outcome of a position with perfect play, for the side to move.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Wdl {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// Represents the answer of a tablebase for a position: the outcome and, if it is not a draw,
+/// the number of plies (half moves) until mate with perfect play.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Probe {
+    pub wdl: Wdl,
+    pub dtm: Option<u32>,
+}
+
+impl Probe {
+    /// Translates a stored value. Distances to mate are odd when the side to move mates, and even
+    /// when it gets mated.
+    fn from_value(value: u8) -> Option<Probe> {
+        match value {
+            ILLEGAL | UNKNOWN => None,
+            DRAW => Some(Probe::DRAW),
+            plies if plies % 2 == 1 => Some(Probe {
+                wdl: Wdl::Win,
+                dtm: Some(plies as u32),
+            }),
+            plies => Some(Probe {
+                wdl: Wdl::Loss,
+                dtm: Some(plies as u32),
+            }),
+        }
+    }
+
+    const DRAW: Probe = Probe {
+        wdl: Wdl::Draw,
+        dtm: None,
+    };
+}
+
+impl fmt::Display for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.wdl, self.dtm) {
+            (Wdl::Win, Some(plies)) => write!(f, "Win, mate in {} plies", plies),
+            (Wdl::Loss, Some(plies)) => write!(f, "Loss, mated in {} plies", plies),
+            _ => write!(f, "Draw"),
+        }
+    }
+}
+
+/// Represents the outcome of every position of an ending, like KQvK, found by retrograde analysis.
+/// There is one value per placement of the pieces and side to move.
+pub struct Tablebase {
+    material: Material,
+    /// Pieces of the material in the order their squares are indexed.
+    pieces: Vec<(Color, PieceKind)>,
+    values: Vec<u8>,
+}
+
+impl Tablebase {
+    /// Builds the tablebase of the material by retrograde analysis: it starts from the checkmates and
+    /// walks the moves backwards, one ply at a time. Captures lead to smaller endings, which are looked
+    /// up in `subtables`; they must be there already.
+    pub fn generate(material: &Material, subtables: &Tablebases) -> Tablebase {
+        let mut table = Tablebase {
+            material: material.clone(),
+            pieces: material.pieces(),
+            values: vec![UNKNOWN; Tablebase::size(material)],
+        };
+        let size = table.values.len();
+
+        // Positions reached without capturing that are still to be proven won for the rival.
+        let mut successors_left = vec![0u8; size];
+        // Longest mate seen so far, in case every move loses.
+        let mut longest_loss = vec![0u8; size];
+        // Positions that can not be lost, because some capture draws or wins.
+        let mut can_not_lose = vec![false; size];
+        let mut by_distance: Vec<Vec<usize>> = Vec::new();
+
+        for index in 0..size {
+            if table.canonical_of(index) != index {
+                continue;
+            }
+            let position = match table.decode(index) {
+                Some(position) => position,
+                None => {
+                    table.values[index] = ILLEGAL;
+                    continue;
+                }
+            };
+            let side = position.side_to_move;
+            if position.in_check(side.opposite()) {
+                table.values[index] = ILLEGAL;
+                continue;
+            }
+
+            let moves = position.legal_moves();
+            if moves.is_empty() {
+                if position.in_check(side) {
+                    push_at(0, index, &mut by_distance);
+                } else {
+                    table.values[index] = DRAW;
+                }
+                continue;
+            }
+
+            let mut shortest_win: Option<u8> = None;
+            let mut successors = Vec::new();
+            for chess_move in &moves {
+                let after = position.play(chess_move);
+                if position.get(&chess_move.to).is_none() {
+                    successors.extend(table.encode(&after));
+                    continue;
+                }
+                match subtables.probe_mailbox(&after).unwrap_or(Probe::DRAW) {
+                    Probe {
+                        wdl: Wdl::Loss,
+                        dtm: Some(plies),
+                    } => {
+                        let plies = plies as u8 + 1;
+                        shortest_win = Some(shortest_win.map_or(plies, |w| w.min(plies)));
+                        can_not_lose[index] = true;
+                    }
+                    Probe {
+                        wdl: Wdl::Win,
+                        dtm: Some(plies),
+                    } => {
+                        longest_loss[index] = longest_loss[index].max(plies as u8 + 1);
+                    }
+                    _ => can_not_lose[index] = true,
+                }
+            }
+            // Symmetric moves lead to the same position, which is proven only once.
+            successors.sort_unstable();
+            successors.dedup();
+            successors_left[index] = successors.len() as u8;
+
+            if let Some(plies) = shortest_win {
+                push_at(plies, index, &mut by_distance);
+            } else if successors.is_empty() && !can_not_lose[index] {
+                push_at(longest_loss[index], index, &mut by_distance);
+            }
+        }
+
+        let mut distance = 0;
+        while distance < by_distance.len() {
+            for index in std::mem::take(&mut by_distance[distance]) {
+                if table.values[index] != UNKNOWN {
+                    continue;
+                }
+                table.values[index] = distance as u8;
+
+                for previous in table.predecessors(index) {
+                    if table.values[previous] != UNKNOWN {
+                        continue;
+                    }
+                    if distance % 2 == 0 {
+                        // The rival gets mated after this move, so the previous position is won.
+                        push_at(distance as u8 + 1, previous, &mut by_distance);
+                    } else {
+                        successors_left[previous] -= 1;
+                        longest_loss[previous] = longest_loss[previous].max(distance as u8 + 1);
+                        if successors_left[previous] == 0 && !can_not_lose[previous] {
+                            push_at(longest_loss[previous], previous, &mut by_distance);
+                        }
+                    }
+                }
+            }
+            distance += 1;
+        }
+
+        // Whatever could not be proven won or lost is a draw. Symmetric positions copy their value.
+        for index in 0..size {
+            let canonical = table.canonical_of(index);
+            table.values[index] = match table.values[canonical] {
+                UNKNOWN => DRAW,
+                value => value,
+            };
+        }
+        table
+    }
+
+    /// Returns the material of the ending
+    pub fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns the outcome of the position for the side to move.
+    /// Returns None if the position does not have this material or can not happen in a game.
+    pub fn probe(&self, position: &Mailbox) -> Option<Probe> {
+        let index = self.encode(position)?;
+        Probe::from_value(self.values[index])
+    }
+
+    /// Returns the number of placements of the pieces with the given outcome when the color moves.
+    pub fn count(&self, color: Color, wdl: Wdl) -> usize {
+        let side = match color {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+        (0..self.values.len())
+            .filter(|i| i % 2 == side)
+            .filter_map(|i| Probe::from_value(self.values[i]))
+            .filter(|probe| probe.wdl == wdl)
+            .count()
+    }
+
+    /// Returns the longest distance to mate in plies.
+    pub fn longest_mate(&self) -> u32 {
+        self.values
+            .iter()
+            .filter(|v| **v < UNKNOWN)
+            .map(|v| *v as u32)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Writes the tablebase to a binary file: a header with the material, then one byte per position.
+    pub fn save(&self, path: &Path) -> Result<(), ChessError<'static>> {
+        let name = self.material.to_string();
+        let mut bytes = Vec::with_capacity(self.values.len() + 32);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(name.len() as u8);
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&(self.values.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.values);
+        fs::write(path, bytes).map_err(|_| ChessError::TABLEBASE_FILE)
+    }
+
+    /// Reads a tablebase written by `save`.
+    pub fn load(path: &Path) -> Result<Tablebase, ChessError<'static>> {
+        let bytes = fs::read(path).map_err(|_| ChessError::TABLEBASE_FILE)?;
+        let rest = bytes
+            .strip_prefix(MAGIC)
+            .ok_or(ChessError::TABLEBASE_FILE)?;
+        let (length, rest) = rest.split_first().ok_or(ChessError::TABLEBASE_FILE)?;
+        let length = *length as usize;
+        if rest.len() < length + 8 {
+            return Err(ChessError::TABLEBASE_FILE);
+        }
+
+        let name = std::str::from_utf8(&rest[..length]).map_err(|_| ChessError::TABLEBASE_FILE)?;
+        let material: Material = name.parse()?;
+        let mut size = [0u8; 8];
+        size.copy_from_slice(&rest[length..length + 8]);
+        let values = &rest[length + 8..];
+        if u64::from_le_bytes(size) as usize != values.len()
+            || values.len() != Tablebase::size(&material)
+        {
+            return Err(ChessError::TABLEBASE_FILE);
+        }
+
+        Ok(Tablebase {
+            pieces: material.pieces(),
+            material,
+            values: values.to_vec(),
+        })
+    }
+
+    /// Returns the number of positions: one per square of each piece, for each side to move.
+    fn size(material: &Material) -> usize {
+        2 * 64usize.pow(material.pieces().len() as u32)
+    }
+
+    /// Returns the index of the position: the side to move, then the square of each piece.
+    fn encode(&self, position: &Mailbox) -> Option<usize> {
+        let count = self.pieces.len();
+        let mut squares = [0; MAX_PIECES];
+        let mut placed = [false; MAX_PIECES];
+        let mut found = 0;
+        for (square, color, kind) in position.pieces() {
+            let slot = (0..count).find(|i| !placed[*i] && self.pieces[*i] == (color, kind))?;
+            placed[slot] = true;
+            squares[slot] = square.index();
+            found += 1;
+        }
+        if found != count {
+            return None;
+        }
+
+        let side = match position.side_to_move {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+        Some(self.canonical(side, &squares[..count]))
+    }
+
+    /// Returns the index stored for the position with the given side to move and squares.
+    /// Without pawns and castling, rotating or mirroring the table keeps the outcome, so every
+    /// position is stored under the smallest index among its 8 symmetric copies. Pieces of the same
+    /// kind and color are taken from a1 to h8.
+    fn canonical(&self, side: usize, squares: &[usize]) -> usize {
+        let mut best = usize::MAX;
+        for symmetry in 0..8 {
+            let mut moved = [0; MAX_PIECES];
+            for (i, square) in squares.iter().enumerate() {
+                moved[i] = transform(*square, symmetry);
+            }
+            for i in 1..squares.len() {
+                let mut j = i;
+                while j > 0 && self.pieces[j] == self.pieces[j - 1] && moved[j] < moved[j - 1] {
+                    moved.swap(j, j - 1);
+                    j -= 1;
+                }
+            }
+            let index = moved[..squares.len()]
+                .iter()
+                .rev()
+                .fold(0, |index, square| index * 64 + square);
+            best = best.min(index * 2 + side);
+        }
+        best
+    }
+
+    /// Returns the index stored for the position of the given index.
+    fn canonical_of(&self, index: usize) -> usize {
+        let mut squares = [0; MAX_PIECES];
+        let mut rest = index / 2;
+        for square in squares.iter_mut().take(self.pieces.len()) {
+            *square = rest % 64;
+            rest /= 64;
+        }
+        self.canonical(index % 2, &squares[..self.pieces.len()])
+    }
+
+    /// Returns the position of the index, or None if two pieces share a square.
+    fn decode(&self, index: usize) -> Option<Mailbox> {
+        let mut position = Mailbox::default();
+        if index % 2 == 1 {
+            position.side_to_move = Color::Black;
+        }
+
+        let mut rest = index / 2;
+        for piece in &self.pieces {
+            let square = Position::from_index(rest % 64);
+            if position.get(&square).is_some() {
+                return None;
+            }
+            position.set(&square, Some(*piece));
+            rest /= 64;
+        }
+        Some(position)
+    }
+
+    /// Returns the indexes of the legal positions that lead to this one with a move that does not capture.
+    /// Without pawns every move can be walked backwards the same way it is walked forwards.
+    fn predecessors(&self, index: usize) -> Vec<usize> {
+        let position = match self.decode(index) {
+            Some(position) => position,
+            None => return Vec::new(),
+        };
+        let side = position.side_to_move;
+        let mut backwards = position;
+        backwards.side_to_move = side.opposite();
+
+        let mut previous: Vec<usize> = backwards
+            .pseudo_legal_moves()
+            .iter()
+            .filter(|m| position.get(&m.to).is_none())
+            .filter_map(|m| {
+                let mut previous = backwards.play(m);
+                previous.side_to_move = side.opposite();
+                if previous.in_check(side) {
+                    return None;
+                }
+                self.encode(&previous)
+            })
+            .collect();
+        previous.sort_unstable();
+        previous.dedup();
+        previous
+    }
+}
+
+/// Represents a set of tablebases, so positions of any of their endings can be probed.
+#[derive(Default)]
+pub struct Tablebases {
+    tables: HashMap<Material, Tablebase>,
+}
+
+impl Tablebases {
+    /// Builds the tablebase of the material, and first the ones of the endings its captures lead to.
+    pub fn generate(&mut self, material: &Material) {
+        if material.only_kings() || self.contains(material) {
+            return;
+        }
+        for smaller in material.after_captures() {
+            self.generate(&smaller);
+        }
+        let table = Tablebase::generate(material, self);
+        self.insert(table);
+    }
+
+    /// Adds a tablebase, for example one read from a file.
+    pub fn insert(&mut self, table: Tablebase) {
+        self.tables.insert(table.material().clone(), table);
+    }
+
+    /// Returns the tablebase of the material, if there is one.
+    pub fn get(&self, material: &Material) -> Option<&Tablebase> {
+        self.tables.get(material)
+    }
+
+    /// Returns true if the material, or the same one with the colors swapped, can be probed.
+    pub fn contains(&self, material: &Material) -> bool {
+        self.tables.contains_key(material) || self.tables.contains_key(&material.swapped())
+    }
+
+    /// Returns the outcome of the Board for the side to move, if its ending is in the set.
+    pub fn probe(&self, board: &Board) -> Option<Probe> {
+        self.probe_mailbox(&Mailbox::from_board(board))
+    }
+
+    /// Returns the outcome of the position for the side to move, if its ending is in the set.
+    /// Two bare kings are always a draw.
+    pub fn probe_mailbox(&self, position: &Mailbox) -> Option<Probe> {
+        let material = Material::of(position).ok()?;
+        if material.only_kings() {
+            return Some(Probe::DRAW);
+        }
+        if let Some(table) = self.tables.get(&material) {
+            return table.probe(position);
+        }
+        // Without pawns, swapping the colors of every piece and of the side to move keeps the outcome.
+        let table = self.tables.get(&material.swapped())?;
+        table.probe(&swap_colors(position))
+    }
+
+    /// Returns the best move of the Board: the fastest mate when winning, a drawing move when it is
+    /// possible, and the longest resistance when losing.
+    pub fn best_move(&self, board: &Board) -> Option<Move> {
+        let position = Mailbox::from_board(board);
+        let mut best: Option<(i64, Move)> = None;
+        for chess_move in position.legal_moves() {
+            let after = match self.probe_mailbox(&position.play(&chess_move)) {
+                Some(after) => after,
+                None => continue,
+            };
+            let plies = after.dtm.unwrap_or(0) as i64;
+            let score = match after.wdl {
+                Wdl::Loss => 1000 - plies,
+                Wdl::Draw => 0,
+                Wdl::Win => plies - 1000,
+            };
+            if best.as_ref().is_none_or(|(s, _)| score > *s) {
+                best = Some((score, chess_move));
+            }
+        }
+        best.map(|(_, chess_move)| chess_move)
+    }
+}
+
+/// Returns the square seen after mirroring the table horizontally (bit 1), vertically (bit 2) and
+/// along the a1-h8 diagonal (bit 4).
+fn transform(square: usize, symmetry: usize) -> usize {
+    let (mut x, mut y) = (square % 8, square / 8);
+    if symmetry & 1 != 0 {
+        x = 7 - x;
+    }
+    if symmetry & 2 != 0 {
+        y = 7 - y;
+    }
+    if symmetry & 4 != 0 {
+        std::mem::swap(&mut x, &mut y);
+    }
+    y * 8 + x
+}
+
+/// Adds the position to the ones whose distance to mate is the given one.
+fn push_at(distance: u8, index: usize, by_distance: &mut Vec<Vec<usize>>) {
+    let distance = distance as usize;
+    if by_distance.len() <= distance {
+        by_distance.resize(distance + 1, Vec::new());
+    }
+    by_distance[distance].push(index);
+}
+
+/// Returns the position with every piece, and the side to move, of the other color.
+fn swap_colors(position: &Mailbox) -> Mailbox {
+    let mut swapped = Mailbox::default();
+    swapped.side_to_move = position.side_to_move.opposite();
+    for (square, color, kind) in position.pieces() {
+        swapped.set(&square, Some((color.opposite(), kind)));
+    }
+    swapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::OnceLock;
+
+    /// KQvK and KRvK, generated once for every test.
+    fn tablebases() -> &'static Tablebases {
+        static TABLEBASES: OnceLock<Tablebases> = OnceLock::new();
+        TABLEBASES.get_or_init(|| {
+            let mut tablebases = Tablebases::default();
+            tablebases.generate(&"KQvK".parse().unwrap());
+            tablebases.generate(&"KRvK".parse().unwrap());
+            tablebases
+        })
+    }
+
+    fn probe(tablebases: &Tablebases, fen: &str) -> Option<Probe> {
+        tablebases.probe(&Board::from_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn test_kqk() {
+        let tablebases = tablebases();
+        let table = tablebases.get(&"KQvK".parse().unwrap()).unwrap();
+
+        // The longest KQvK mate takes 10 moves: 20 plies if Black moves first.
+        assert_eq!(table.longest_mate(), 20);
+        assert_eq!(table.count(Color::Black, Wdl::Win), 0);
+        assert_eq!(
+            probe(tablebases, "7k/8/6K1/8/8/8/8/1Q6 w - - 0 1"),
+            Some(Probe {
+                wdl: Wdl::Win,
+                dtm: Some(1)
+            })
+        );
+        assert_eq!(
+            probe(tablebases, "Q6k/8/6K1/8/8/8/8/8 b - - 0 1"),
+            Some(Probe {
+                wdl: Wdl::Loss,
+                dtm: Some(0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_stalemate_and_capture_are_draws() {
+        let tablebases = tablebases();
+
+        assert_eq!(
+            probe(tablebases, "7k/8/6QK/8/8/8/8/8 b - - 0 1"),
+            Some(Probe::DRAW)
+        );
+        // The black king takes the queen.
+        assert_eq!(
+            probe(tablebases, "8/8/8/8/8/8/1Q6/k6K b - - 0 1"),
+            Some(Probe::DRAW)
+        );
+    }
+
+    #[test]
+    fn test_swapped_colors_and_illegal_positions() {
+        let tablebases = tablebases();
+
+        assert_eq!(
+            probe(tablebases, "q6K/8/6k1/8/8/8/8/8 w - - 0 1").map(|p| p.wdl),
+            Some(Wdl::Loss)
+        );
+        assert_eq!(probe(tablebases, "7k/6K1/8/8/8/8/8/1Q6 w - - 0 1"), None);
+        assert_eq!(probe(tablebases, "7k/8/6K1/8/8/8/8/1B6 w - - 0 1"), None);
+    }
+
+    #[test]
+    fn test_best_move_mates() {
+        let tablebases = tablebases();
+        let board = Board::from_fen("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1").unwrap();
+
+        let best = tablebases.best_move(&board).unwrap();
+        let after = Mailbox::from_board(&board).play(&best);
+
+        assert!(after.is_checkmate());
+    }
+
+    #[test]
+    fn test_krk() {
+        let tablebases = tablebases();
+        let table = tablebases.get(&"KRvK".parse().unwrap()).unwrap();
+
+        // The longest KRvK mate takes 16 moves: 32 plies if Black moves first.
+        assert_eq!(table.longest_mate(), 32);
+        // The rook is lost at once, so it is a draw.
+        assert_eq!(
+            probe(tablebases, "8/8/8/8/8/8/1k6/1R5K b - - 0 1").map(|p| p.wdl),
+            Some(Wdl::Draw)
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let tablebases = tablebases();
+        let table = tablebases.get(&"KQvK".parse().unwrap()).unwrap();
+        let path = std::env::temp_dir().join(format!("kqvk-{}.tb", std::process::id()));
+
+        table.save(&path).unwrap();
+        let loaded = Tablebase::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.material(), table.material());
+        assert_eq!(loaded.values, table.values);
+    }
+
+    #[test]
+    fn test_load_broken_file() {
+        let path = std::env::temp_dir().join(format!("broken-{}.tb", std::process::id()));
+        fs::write(&path, b"CHESSTB1\x04KQvK").unwrap();
+
+        assert_eq!(
+            Tablebase::load(&path).err(),
+            Some(ChessError::TABLEBASE_FILE)
+        );
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            Tablebase::load(&path).err(),
+            Some(ChessError::TABLEBASE_FILE)
+        );
+    }
+}
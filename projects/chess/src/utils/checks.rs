@@ -1,32 +1,20 @@
+use crate::board_mod::castling_rights::CastlingSide;
 use crate::color::Color;
 use crate::errors::chess_error::ChessError;
+use crate::errors::position_error::PositionError;
+use crate::move_gen_mod::mailbox::Mailbox;
 use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
 
 /// Returns true if the square has a piece. False otherwise.
 pub fn square_has_piece(sq: &str) -> bool {
     sq != "_"
 }
 
-/// Returns Ok if the number of rows iterated was 8 and the Pieces found are valid. Err otherwise. 
-pub fn result_is_valid(rows: i32, pieces: &PieceList) -> Result<&str, &ChessError> {
-    if !row_size_is_valid(rows) {
-        return Err(&ChessError::TABLE_SIZE);
-    }
-
-    match pieces_pass_checks(pieces) {
-        Ok(_) => Ok(""),
-        Err(e) => Err(e),
-    }
-}
-
-/// Returns true if the number of iterated rows was 8.
-fn row_size_is_valid(curr_rows: i32) -> bool {
-    curr_rows == -1
-}
-
 /// Returns true if the Pieces in the PieceList pass all needed checks: total count and color.
-fn pieces_pass_checks(pieces: &PieceList) -> Result<&str, &ChessError> {
-    if pieces.len != 2 {
+pub fn pieces_pass_checks(pieces: &PieceList) -> Result<&str, &ChessError<'_>> {
+    if pieces.len() != 2 {
         return Err(&ChessError::NUMBER_PIECES);
     }
 
@@ -39,8 +27,10 @@ fn pieces_pass_checks(pieces: &PieceList) -> Result<&str, &ChessError> {
 
 /// Returns true if the Pieces in the PieceList have different colors: black and white.
 fn pieces_checked_color(pieces: &PieceList) -> bool {
-    let first_piece = &pieces.first();
-    let second_piece = &pieces.second();
+    let (first_piece, second_piece) = match (pieces.first(), pieces.second()) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return false,
+    };
 
     let black_and_white = first_piece.color == Color::Black && second_piece.color == Color::White;
     let white_and_black = first_piece.color == Color::White && second_piece.color == Color::Black;
@@ -48,70 +38,140 @@ fn pieces_checked_color(pieces: &PieceList) -> bool {
     (black_and_white) || (white_and_black)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::piece_mod::piece::Piece;
-
-    #[test]
-    fn test_square_has_piece_true() {
-        square_has_piece(&"k");
+/// Returns Ok if the position could happen in a game: each side has one king, pawns are not on the
+/// first or last rank, the piece counts can be reached with promotions, castling rights and the en
+/// passant square agree with the pieces, and the side that just moved is not in check.
+pub fn position_pass_checks(position: &Mailbox) -> Result<(), PositionError> {
+    for color in [Color::White, Color::Black] {
+        let kings = count(position, color, |_, kind| kind == PieceKind::King);
+        match kings {
+            0 => return Err(PositionError::MissingKing(color)),
+            1 => {}
+            _ => return Err(PositionError::TooManyKings(color)),
+        }
     }
 
-    #[test]
-    fn test_square_has_piece_false() {
-        square_has_piece(&"_");
+    if let Some((square, _, _)) = position
+        .pieces()
+        .find(|(square, _, kind)| *kind == PieceKind::Pawn && (square.y == 0 || square.y == 7))
+    {
+        return Err(PositionError::PawnOnBackRank(square));
     }
 
-    #[test]
-    fn test_result_is_valid_true() {
-        let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
+    for color in [Color::White, Color::Black] {
+        material_is_possible(position, color)?;
+        castling_is_possible(position, color)?;
+    }
+    en_passant_is_possible(position)?;
 
-        piece_list.push(piece1);
-        piece_list.push(piece2);
+    let opponent = position.side_to_move.opposite();
+    if position.in_check(opponent) {
+        return Err(PositionError::OpponentInCheck(opponent));
+    }
+    Ok(())
+}
 
-        let res = result_is_valid(-1, &piece_list);
+/// Returns the number of pieces of the color that match the condition.
+fn count(
+    position: &Mailbox,
+    color: Color,
+    condition: impl Fn(Position, PieceKind) -> bool,
+) -> usize {
+    position
+        .pieces()
+        .filter(|(square, c, kind)| *c == color && condition(*square, *kind))
+        .count()
+}
 
-        assert!(res.is_ok());
+/// Checks that every piece beyond the starting ones can come from a promotion of a missing pawn.
+/// Bishops are counted by the color of their squares, as a side starts with one of each.
+fn material_is_possible(position: &Mailbox, color: Color) -> Result<(), PositionError> {
+    let pawns = count(position, color, |_, kind| kind == PieceKind::Pawn);
+    if pawns > 8 {
+        return Err(PositionError::TooManyPawns(color));
     }
 
-    #[test]
-    fn test_result_is_valid_false() {
-        let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
-        let piece3 = Piece::new(&"t", 1, 1).unwrap();
-
-        piece_list.push(piece1);
-        piece_list.push(piece2);
-        piece_list.push(piece3);
+    let of_kind = |wanted: PieceKind| count(position, color, |_, kind| kind == wanted);
+    let bishops_on = |light: bool| {
+        count(position, color, |square, kind| {
+            kind == PieceKind::Bishop && ((square.x + square.y) % 2 == 1) == light
+        })
+    };
+    let promoted = of_kind(PieceKind::Queen).saturating_sub(1)
+        + of_kind(PieceKind::Rook).saturating_sub(2)
+        + of_kind(PieceKind::Knight).saturating_sub(2)
+        + bishops_on(true).saturating_sub(1)
+        + bishops_on(false).saturating_sub(1);
+    if promoted > 8 - pawns {
+        return Err(PositionError::ImpossiblePromotions(color));
+    }
+    Ok(())
+}
 
-        let res = result_is_valid(-1, &piece_list);
+/// Checks that the king is on its starting rank with the castling rook on the right side of it.
+fn castling_is_possible(position: &Mailbox, color: Color) -> Result<(), PositionError> {
+    for side in CastlingSide::ALL {
+        let file = match position.castling.get(color, side) {
+            Some(file) => file,
+            None => continue,
+        };
+        let rank = color.back_rank();
+        let king = position.king(color).filter(|king| king.y == rank);
+        let rook = Position { x: file, y: rank };
+        let in_place = king.is_some_and(|king| match side {
+            CastlingSide::King => file > king.x,
+            CastlingSide::Queen => file < king.x,
+        }) && position.get(&rook) == Some((color, PieceKind::Rook));
+        if !in_place {
+            return Err(PositionError::InvalidCastling(color, side));
+        }
+    }
+    Ok(())
+}
 
-        assert!(res.is_err());
+/// Checks that the en passant square is empty, on the right rank, and was just passed over by a
+/// rival pawn that moved two squares.
+fn en_passant_is_possible(position: &Mailbox) -> Result<(), PositionError> {
+    let square = match position.en_passant {
+        Some(square) => square,
+        None => return Ok(()),
+    };
+    let rival = position.side_to_move.opposite();
+    let direction = rival.pawn_direction();
+    let valid = square.y == rival.back_rank() + 2 * direction
+        && position.get(&square).is_none()
+        && square
+            .offset(0, -direction)
+            .is_some_and(|from| position.get(&from).is_none())
+        && square
+            .offset(0, direction)
+            .is_some_and(|pawn| position.get(&pawn) == Some((rival, PieceKind::Pawn)));
+    if !valid {
+        return Err(PositionError::InvalidEnPassant(square));
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_row_size_is_valid_true() {
-        let res = row_size_is_valid(-1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::piece_mod::piece::Piece;
 
-        assert!(res);
+    #[test]
+    fn test_square_has_piece_true() {
+        square_has_piece("k");
     }
 
     #[test]
-    fn test_row_size_is_valid_false() {
-        let res = row_size_is_valid(-10);
-
-        assert!(!res);
+    fn test_square_has_piece_false() {
+        square_has_piece("_");
     }
 
     #[test]
     fn test_pieces_checked_color_true() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"r", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("r", 2, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -124,8 +184,8 @@ mod tests {
     #[test]
     fn test_pieces_checked_color_false() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"D", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("D", 2, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -137,8 +197,8 @@ mod tests {
     #[test]
     fn test_pieces_pass_checks_true() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"d", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("d", 2, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -151,8 +211,8 @@ mod tests {
     #[test]
     fn test_pieces_pass_checks_false_color() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"D", 2, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("D", 2, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -164,9 +224,9 @@ mod tests {
     #[test]
     fn test_pieces_pass_checks_false_number() {
         let mut piece_list = PieceList::init();
-        let piece1 = Piece::new(&"R", 1, 2).unwrap();
-        let piece2 = Piece::new(&"d", 2, 1).unwrap();
-        let piece3 = Piece::new(&"t", 4, 1).unwrap();
+        let piece1 = Piece::new("R", 1, 2).unwrap();
+        let piece2 = Piece::new("d", 2, 1).unwrap();
+        let piece3 = Piece::new("t", 4, 1).unwrap();
 
         piece_list.push(piece1);
         piece_list.push(piece2);
@@ -175,4 +235,128 @@ mod tests {
         let res = pieces_pass_checks(&piece_list);
         assert!(res.is_err());
     }
+
+    fn position(fen: &str) -> Mailbox {
+        Mailbox::from_board(&crate::board_mod::board::Board::from_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn test_position_pass_checks() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "QQQQQQQQ/Q7/8/8/8/8/8/K6k b - - 0 1",
+        ] {
+            assert_eq!(position_pass_checks(&position(fen)), Ok(()), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_kings() {
+        assert_eq!(
+            position_pass_checks(&position("8/8/8/8/8/8/8/K7 w - - 0 1")),
+            Err(PositionError::MissingKing(Color::Black))
+        );
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/8/K6K w - - 0 1")),
+            Err(PositionError::TooManyKings(Color::White))
+        );
+    }
+
+    #[test]
+    fn test_pawn_on_back_rank() {
+        assert_eq!(
+            position_pass_checks(&position("k3P3/8/8/8/8/8/8/K7 w - - 0 1")),
+            Err(PositionError::PawnOnBackRank(Position { x: 4, y: 7 }))
+        );
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/8/K2p4 w - - 0 1")),
+            Err(PositionError::PawnOnBackRank(Position { x: 3, y: 0 }))
+        );
+    }
+
+    #[test]
+    fn test_impossible_piece_counts() {
+        assert_eq!(
+            position_pass_checks(&position("k7/pppppppp/p7/8/8/8/8/K7 w - - 0 1")),
+            Err(PositionError::TooManyPawns(Color::Black))
+        );
+        // Seven pawns left, so only one promotion, but two extra queens.
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/PPPPPPP1/KQQQ4 w - - 0 1")),
+            Err(PositionError::ImpossiblePromotions(Color::White))
+        );
+        // Two bishops on light squares need a promotion, and no pawn is missing.
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/PPPPPPPP/KB1B4 w - - 0 1")),
+            Err(PositionError::ImpossiblePromotions(Color::White))
+        );
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/PPPPPPPP/KBB5 w - - 0 1")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_castling_rights() {
+        let mut without_rook = position("r3k3/8/8/8/8/8/8/R3K2R w KQq - 0 1");
+        without_rook
+            .castling
+            .set(Color::Black, CastlingSide::King, Some(7));
+        assert_eq!(
+            position_pass_checks(&without_rook),
+            Err(PositionError::InvalidCastling(
+                Color::Black,
+                CastlingSide::King
+            ))
+        );
+
+        let mut king_moved = position("r3k2r/8/8/8/8/8/8/R4K1R w kq - 0 1");
+        king_moved
+            .castling
+            .set(Color::White, CastlingSide::Queen, Some(0));
+        assert_eq!(position_pass_checks(&king_moved), Ok(()));
+        king_moved
+            .castling
+            .set(Color::White, CastlingSide::King, Some(0));
+        assert_eq!(
+            position_pass_checks(&king_moved),
+            Err(PositionError::InvalidCastling(
+                Color::White,
+                CastlingSide::King
+            ))
+        );
+    }
+
+    #[test]
+    fn test_en_passant() {
+        let mut valid = position("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3");
+        assert_eq!(position_pass_checks(&valid), Ok(()));
+
+        let d3 = Position { x: 3, y: 2 };
+        valid.en_passant = Some(d3);
+        assert_eq!(
+            position_pass_checks(&valid),
+            Err(PositionError::InvalidEnPassant(d3))
+        );
+        let e6 = Position { x: 4, y: 5 };
+        valid.en_passant = Some(e6);
+        assert_eq!(
+            position_pass_checks(&valid),
+            Err(PositionError::InvalidEnPassant(e6))
+        );
+    }
+
+    #[test]
+    fn test_side_not_to_move_in_check() {
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/8/K6r b - - 0 1")),
+            Err(PositionError::OpponentInCheck(Color::White))
+        );
+        assert_eq!(
+            position_pass_checks(&position("k7/8/8/8/8/8/8/K6r w - - 0 1")),
+            Ok(())
+        );
+    }
 }
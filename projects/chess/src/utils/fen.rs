@@ -0,0 +1,318 @@
+use crate::board_mod::board::Board;
+use crate::board_mod::castling_rights::{CastlingRights, CastlingSide};
+use crate::color::Color;
+use crate::errors::chess_error::ChessError;
+use crate::piece_list_mod::piece_list::PieceList;
+use crate::piece_mod::piece::Piece;
+use crate::piece_type_mod::piece_kind::PieceKind;
+use crate::position_mod::position::Position;
+
+/// Parses a FEN string and returns the Board it describes. Err otherwise.
+/// The move counters are optional and default to 0 and 1.
+pub fn parse_fen(fen: &str) -> Result<Board, ChessError<'static>> {
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() < 4 || fields.len() > 6 {
+        return Err(ChessError::FEN);
+    }
+
+    let mut board = Board::from_pieces(parse_placement(fields[0])?)?;
+    let side_to_move = match fields[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => return Err(ChessError::FEN),
+    };
+    let castling = parse_castling(fields[2], &board)?;
+    let en_passant = match fields[3] {
+        "-" => None,
+        square => Some(Position::from_algebraic(square).ok_or(ChessError::FEN)?),
+    };
+    let halfmove_clock = parse_counter(fields.get(4), 0)?;
+    let fullmove_number = parse_counter(fields.get(5), 1)?;
+
+    board.set_state(
+        side_to_move,
+        castling,
+        en_passant,
+        halfmove_clock,
+        fullmove_number,
+    );
+    Ok(board)
+}
+
+/// Returns the Board in FEN notation.
+/// Castling rights are written as KQkq when the rook is the outermost one on its side of the king,
+/// and as the file of the rook otherwise, so every Chess960 position can be written.
+pub fn to_fen(board: &Board) -> String {
+    let mut rows = Vec::new();
+    for y in (0..8).rev() {
+        let mut row = String::new();
+        let mut empty = 0;
+        for x in 0..8 {
+            match board.at(&Position { x, y }) {
+                Some(piece) => {
+                    if empty > 0 {
+                        row.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    row.push(piece.get_type().kind().fen_char(piece.color));
+                }
+                None => empty += 1,
+            }
+        }
+        if empty > 0 {
+            row.push_str(&empty.to_string());
+        }
+        rows.push(row);
+    }
+
+    let side_to_move = match board.side_to_move() {
+        Color::White => "w",
+        Color::Black => "b",
+    };
+    let en_passant = match board.en_passant() {
+        Some(square) => square.to_algebraic(),
+        None => "-".to_string(),
+    };
+
+    format!(
+        "{} {} {} {} {} {}",
+        rows.join("/"),
+        side_to_move,
+        castling_to_fen(board),
+        en_passant,
+        board.halfmove_clock(),
+        board.fullmove_number()
+    )
+}
+
+/// Parses the piece placement field: eight ranks from 8 to 1, separated by '/'.
+fn parse_placement(placement: &str) -> Result<PieceList, ChessError<'static>> {
+    let mut pieces = PieceList::default();
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(ChessError::FEN);
+    }
+
+    for (i, rank) in ranks.iter().enumerate() {
+        let y = 7 - i as i32;
+        let mut x = 0;
+        for c in rank.chars() {
+            if let Some(empty) = c.to_digit(10) {
+                if empty == 0 || empty > 8 {
+                    return Err(ChessError::FEN);
+                }
+                x += empty as i32;
+            } else {
+                let (kind, color) = PieceKind::from_fen_char(c).ok_or(ChessError::FEN)?;
+                if x > 7 {
+                    return Err(ChessError::FEN);
+                }
+                pieces.push(Piece::from_kind(kind, color, Position { x, y }));
+                x += 1;
+            }
+        }
+        if x != 8 {
+            return Err(ChessError::FEN);
+        }
+    }
+
+    Ok(pieces)
+}
+
+/// Parses the castling field. It accepts KQkq, the rook files (Shredder-FEN) or '-'.
+fn parse_castling(field: &str, board: &Board) -> Result<CastlingRights, ChessError<'static>> {
+    let mut rights = CastlingRights::default();
+    if field == "-" {
+        return Ok(rights);
+    }
+
+    for c in field.chars() {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let king_file = king_file(board, color).ok_or(ChessError::FEN)?;
+        let (side, rook_file) = match c.to_ascii_lowercase() {
+            'k' => (
+                CastlingSide::King,
+                outermost_rook(board, color, CastlingSide::King, king_file),
+            ),
+            'q' => (
+                CastlingSide::Queen,
+                outermost_rook(board, color, CastlingSide::Queen, king_file),
+            ),
+            'a'..='h' => {
+                let file = c.to_ascii_lowercase() as i32 - 'a' as i32;
+                let side = if file > king_file {
+                    CastlingSide::King
+                } else {
+                    CastlingSide::Queen
+                };
+                let rook = Some(file).filter(|f| is_rook(board, color, *f));
+                (side, rook)
+            }
+            _ => return Err(ChessError::FEN),
+        };
+        let rook_file = rook_file.ok_or(ChessError::FEN)?;
+        rights.set(color, side, Some(rook_file));
+    }
+
+    Ok(rights)
+}
+
+/// Writes the castling field, White's rights first.
+fn castling_to_fen(board: &Board) -> String {
+    let rights = board.castling();
+    let mut field = String::new();
+    for color in [Color::White, Color::Black] {
+        for side in CastlingSide::ALL {
+            let rook_file = match rights.get(color, side) {
+                Some(file) => file,
+                None => continue,
+            };
+            let outermost =
+                king_file(board, color).and_then(|king| outermost_rook(board, color, side, king));
+            let letter = if outermost == Some(rook_file) {
+                match side {
+                    CastlingSide::King => 'K',
+                    CastlingSide::Queen => 'Q',
+                }
+            } else {
+                (b'A' + rook_file as u8) as char
+            };
+            match color {
+                Color::White => field.push(letter),
+                Color::Black => field.push(letter.to_ascii_lowercase()),
+            }
+        }
+    }
+
+    if field.is_empty() {
+        field.push('-');
+    }
+    field
+}
+
+/// Returns the file of the king of the given color, if it stands on its back rank.
+fn king_file(board: &Board, color: Color) -> Option<i32> {
+    (0..8).find(|x| {
+        board
+            .at(&Position {
+                x: *x,
+                y: color.back_rank(),
+            })
+            .is_some_and(|p| p.color == color && p.get_type().kind() == PieceKind::King)
+    })
+}
+
+/// Returns true if a rook of the given color stands on the given file of its back rank.
+fn is_rook(board: &Board, color: Color, file: i32) -> bool {
+    board
+        .at(&Position {
+            x: file,
+            y: color.back_rank(),
+        })
+        .is_some_and(|p| p.color == color && p.get_type().kind() == PieceKind::Rook)
+}
+
+/// Returns the file of the rook farthest from the king on the given side of its back rank.
+fn outermost_rook(board: &Board, color: Color, side: CastlingSide, king_file: i32) -> Option<i32> {
+    match side {
+        CastlingSide::King => (king_file + 1..8).rev().find(|f| is_rook(board, color, *f)),
+        CastlingSide::Queen => (0..king_file).find(|f| is_rook(board, color, *f)),
+    }
+}
+
+/// Parses a move counter. If it is missing, the default value is used.
+fn parse_counter(field: Option<&&str>, default: u32) -> Result<u32, ChessError<'static>> {
+    match field {
+        Some(value) => value.parse().map_err(|_| ChessError::FEN),
+        None => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    #[test]
+    fn test_start_position() {
+        let board = parse_fen(START).unwrap();
+
+        assert_eq!(board.len(), 32);
+        assert_eq!(board.castling(), CastlingRights::classical());
+        assert_eq!(
+            board
+                .at(&Position { x: 4, y: 7 })
+                .unwrap()
+                .get_type()
+                .string(),
+            "King"
+        );
+        assert_eq!(to_fen(&board), START);
+    }
+
+    #[test]
+    fn test_state_fields() {
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 3";
+        let board = parse_fen(fen).unwrap();
+
+        assert_eq!(board.side_to_move(), Color::White);
+        assert_eq!(board.en_passant(), Position::from_algebraic("d6"));
+        assert!(board.castling().is_empty());
+        assert_eq!(board.fullmove_number(), 3);
+        assert_eq!(to_fen(&board), fen);
+    }
+
+    #[test]
+    fn test_counters_are_optional() {
+        let board = parse_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+
+        assert_eq!(board.side_to_move(), Color::Black);
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 1);
+    }
+
+    #[test]
+    fn test_shredder_castling() {
+        let board = parse_fen("1r2k1r1/8/8/8/8/8/8/RR2K3 w Bgb - 0 1").unwrap();
+
+        assert_eq!(
+            board.castling().get(Color::White, CastlingSide::Queen),
+            Some(1)
+        );
+        assert_eq!(
+            board.castling().get(Color::Black, CastlingSide::King),
+            Some(6)
+        );
+        assert_eq!(
+            board.castling().get(Color::Black, CastlingSide::Queen),
+            Some(1)
+        );
+        assert_eq!(to_fen(&board), "1r2k1r1/8/8/8/8/8/8/RR2K3 w Bkq - 0 1");
+    }
+
+    #[test]
+    fn test_invalid_fen() {
+        assert_eq!(parse_fen("").err(), Some(ChessError::FEN));
+        assert_eq!(
+            parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1").err(),
+            Some(ChessError::FEN)
+        );
+        assert_eq!(
+            parse_fen("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").err(),
+            Some(ChessError::FEN)
+        );
+        assert_eq!(
+            parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").err(),
+            Some(ChessError::FEN)
+        );
+        assert_eq!(
+            parse_fen("4k3/8/8/8/8/8/8/4K3 w K - 0 1").err(),
+            Some(ChessError::FEN)
+        );
+    }
+}
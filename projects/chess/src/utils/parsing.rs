@@ -1,9 +1,10 @@
+use crate::board_mod::board::Board;
 use crate::errors::chess_error::ChessError;
+use crate::errors::grid_error::GridError;
 use crate::piece_list_mod::piece_list::PieceList;
 use crate::piece_mod::piece::Piece;
-use crate::utils::checks::result_is_valid;
+use crate::utils::checks::pieces_pass_checks;
 use crate::utils::checks::square_has_piece;
-use std::str::SplitWhitespace;
 
 /// Returns the first argument needed
 pub fn parse_config(args: &[String]) -> &str {
@@ -12,28 +13,17 @@ pub fn parse_config(args: &[String]) -> &str {
 
 /// Parses the table and returns a PieceList if it is valid. None otherwise.
 /// It also prints the error if the table is invalid.
-/// It iterates through the rows and calls parse_row for each row.
-/// It also calls result_is_valid to check if the number of rows iterated was 8 and the Pieces found are valid.
+/// It calls parse_grid to read the rows and pieces_pass_checks to check the Pieces found.
 pub fn parse_pieces(table: &str) -> Option<PieceList> {
-    let mut pieces = PieceList::default();
-    let rows = table.split('\n');
-    let mut curr_row = 7;
-    let mut curr_col;
-
-    for r in rows {
-        curr_col = 0;
-        match parse_row(r, curr_row, &mut curr_col, &mut pieces) {
-            Ok(_) => {
-                curr_row -= 1;
-            }
-            Err(e) => {
-                e.print();
-                return None;
-            }
+    let pieces = match parse_grid(table) {
+        Ok(pieces) => pieces,
+        Err(e) => {
+            e.print();
+            return None;
         }
-    }
+    };
 
-    match result_is_valid(curr_row, &pieces) {
+    match pieces_pass_checks(&pieces) {
         Ok(_) => Some(pieces),
         Err(e) => {
             e.print();
@@ -42,46 +32,158 @@ pub fn parse_pieces(table: &str) -> Option<PieceList> {
     }
 }
 
-/// Parses the row given and returns Ok if the row is valid. Err otherwise.
-/// If valid, it adds the Pieces found to the PieceList.
-/// It also updates the current row and column.
-/// The current row is updated by decrementing it.
-/// The current column is updated by incrementing it.
-fn parse_row<'a>(
-    row: &'a str,
+/// Parses the table and returns a Board with every piece found. Err otherwise.
+/// Unlike parse_pieces, it accepts any number of pieces and does not print anything.
+pub fn parse_board(table: &str) -> Result<Board, ChessError<'static>> {
+    let pieces = parse_grid(table).map_err(|e| e.error)?;
+    Board::from_pieces(pieces)
+}
+
+/// Parses the 8 rows of the table and returns every Piece found, or the line and column of the
+/// first error.
+/// Blank lines and comments, from '#' to the end of the line, are skipped, and lines may end in
+/// CRLF. Rows may carry their rank label before or after the squares, like `8 _ _ _ _ _ _ _ _`,
+/// and a line of file labels, `a b c d e f g h`, may come before or after the rows.
+pub fn parse_grid(table: &str) -> Result<PieceList, GridError> {
+    let mut pieces = PieceList::default();
+    let mut curr_row = 7;
+    let mut file_labels: Option<usize> = None;
+    let mut last_line = 0;
+
+    for (i, line) in table.lines().enumerate() {
+        let number = i + 1;
+        let line = line.split('#').next().unwrap_or_default();
+        let tokens = tokens(line);
+        if tokens.is_empty() {
+            continue;
+        }
+        last_line = number;
+
+        if is_file_labels(&tokens) {
+            if curr_row < 7 {
+                file_labels = Some(number);
+            }
+            continue;
+        }
+        if let Some(labels) = file_labels {
+            return Err(grid_error(labels, 1, ChessError::FILE_LABEL));
+        }
+        if curr_row < 0 {
+            return Err(grid_error(number, tokens[0].0, ChessError::TABLE_SIZE));
+        }
+
+        let end = line.trim_end().chars().count() + 1;
+        parse_row(&tokens, curr_row, &mut pieces)
+            .map_err(|(column, error)| grid_error(number, column.unwrap_or(end), error))?;
+        curr_row -= 1;
+    }
+
+    if curr_row >= 0 {
+        return Err(grid_error(last_line + 1, 1, ChessError::TABLE_SIZE));
+    }
+    Ok(pieces)
+}
+
+/// Parses the squares of a row, skipping its rank label, and adds the Pieces found to the
+/// PieceList. On error, it returns the column at fault, or None if the row is too short.
+fn parse_row(
+    tokens: &[(usize, &str)],
     curr_row: i32,
-    curr_col: &'a mut i32,
-    pieces: &'a mut PieceList,
-) -> Result<&'a str, &'a ChessError<'a>> {
-    let squares: SplitWhitespace = row.split_whitespace();
-
-    for s in squares {
-        if *curr_col > 7 {
-            return Err(&ChessError::TABLE_SIZE);
+    pieces: &mut PieceList,
+) -> Result<(), (Option<usize>, ChessError<'static>)> {
+    let mut squares = tokens;
+    if let Some(((column, label), rest)) = squares.split_first() {
+        if is_rank_label(label) {
+            check_rank_label(*column, label, curr_row)?;
+            squares = rest;
+        }
+    }
+    if squares.len() > 8 {
+        if let Some(((column, label), rest)) = squares.split_last() {
+            if is_rank_label(label) {
+                check_rank_label(*column, label, curr_row)?;
+                squares = rest;
+            }
+        }
+    }
+
+    let mut curr_col = 0;
+    for (column, s) in squares {
+        if curr_col > 7 {
+            return Err((Some(*column), ChessError::TABLE_SIZE));
         }
 
         if square_has_piece(s) {
-            let piece = Piece::new(s, *curr_col, curr_row);
-            match piece {
-                Some(p) => {
-                    pieces.push(p);
-                }
-                None => {
-                    return Err(&ChessError::UKNOWN);
-                }
+            match Piece::new(s, curr_col, curr_row) {
+                Some(p) => pieces.push(p),
+                None => return Err((Some(*column), ChessError::UKNOWN)),
             }
         }
-        *curr_col += 1;
+        curr_col += 1;
+    }
+
+    if curr_col < 8 {
+        return Err((None, ChessError::TABLE_SIZE));
+    }
+    Ok(())
+}
+
+/// Splits the line on whitespace, keeping the column where each word starts.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (column, (index, c)) in line.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, index)),
+            (true, Some((first, from))) => {
+                tokens.push((first, &line[from..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((first, from)) = start {
+        tokens.push((first, &line[from..]));
+    }
+    tokens
+}
+
+/// Returns true if the line is made of the file labels, from a to h.
+fn is_file_labels(tokens: &[(usize, &str)]) -> bool {
+    let labels: Vec<String> = tokens.iter().map(|(_, t)| t.to_lowercase()).collect();
+    labels == ["a", "b", "c", "d", "e", "f", "g", "h"]
+}
+
+/// Returns true if the word is a number. Pieces are letters, so numbers can only be rank labels.
+fn is_rank_label(word: &str) -> bool {
+    word.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Returns Ok if the rank label matches the row it labels. Err with its column otherwise.
+fn check_rank_label(
+    column: usize,
+    label: &str,
+    curr_row: i32,
+) -> Result<(), (Option<usize>, ChessError<'static>)> {
+    if label.parse::<i32>().ok() == Some(curr_row + 1) {
+        Ok(())
+    } else {
+        Err((Some(column), ChessError::RANK_LABEL))
+    }
+}
+
+fn grid_error(line: usize, column: usize, error: ChessError<'static>) -> GridError {
+    GridError {
+        line,
+        column,
+        error,
     }
-    Ok("")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::color::Color;
-    use crate::piece_list_mod::piece_list::PieceList;
-    use crate::piece_mod::piece::Piece;
+    use crate::position_mod::position::Position;
 
     #[test]
     fn test_parse_config() {
@@ -95,6 +197,97 @@ mod tests {
 
         let pieces = parse_pieces(table).unwrap();
 
-        assert_eq!(pieces.len, 2);
+        assert_eq!(pieces.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_board_many_pieces() {
+        let table = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ A _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ p _ _ _\n_ _ _ t _ _ _ R";
+
+        let board = parse_board(table).unwrap();
+
+        assert_eq!(board.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_board_wrong_size() {
+        let table = "_ _ _ _ _ _ _ _\n_ _ _ A _ _ _ _";
+
+        assert_eq!(parse_board(table).err(), Some(ChessError::TABLE_SIZE));
+    }
+
+    #[test]
+    fn test_parse_board_unknown_piece() {
+        let table = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ X _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ t _ _ _ _";
+
+        assert_eq!(parse_board(table).err(), Some(ChessError::UKNOWN));
+    }
+
+    const EMPTY_ROW: &str = "_ _ _ _ _ _ _ _";
+
+    fn grid_error(table: &str) -> (usize, usize, ChessError<'static>) {
+        let e = parse_grid(table).err().unwrap();
+        (e.line, e.column, e.error)
+    }
+
+    #[test]
+    fn test_parse_grid_final_newline() {
+        let table = format!("{}\n", [EMPTY_ROW; 8].join("\n"));
+
+        assert!(parse_grid(&table).unwrap().is_empty());
+        assert!(parse_grid(&format!("{}\n\n", table)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_grid_labels_comments_and_crlf() {
+        let pieces = parse_grid(include_str!("../../test/table5.txt")).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+        let positions: Vec<Position> = pieces.iter().map(|p| *p.get_position()).collect();
+        assert!(positions.contains(&Position { x: 3, y: 3 }));
+        assert!(positions.contains(&Position { x: 3, y: 0 }));
+    }
+
+    #[test]
+    fn test_parse_grid_unknown_piece_position() {
+        let table = "# comment\n_ _ _ _ _ _ _ _\n\n7 _ _ X _ _ _ _ _";
+
+        assert_eq!(grid_error(table), (4, 7, ChessError::UKNOWN));
+    }
+
+    #[test]
+    fn test_parse_grid_wrong_rank_label() {
+        let table = format!("8 {}\n6 {}", EMPTY_ROW, EMPTY_ROW);
+
+        assert_eq!(grid_error(&table), (2, 1, ChessError::RANK_LABEL));
+    }
+
+    #[test]
+    fn test_parse_grid_row_sizes() {
+        let long = format!("{}\n{} p", EMPTY_ROW, EMPTY_ROW);
+        let short = format!("{}\n_ _ _", EMPTY_ROW);
+
+        assert_eq!(grid_error(&long), (2, 17, ChessError::TABLE_SIZE));
+        assert_eq!(grid_error(&short), (2, 6, ChessError::TABLE_SIZE));
+    }
+
+    #[test]
+    fn test_parse_grid_row_count() {
+        let missing = format!("{}\n# end\n", [EMPTY_ROW; 7].join("\n"));
+        let extra = [EMPTY_ROW; 9].join("\r\n");
+
+        assert_eq!(grid_error(&missing), (8, 1, ChessError::TABLE_SIZE));
+        assert_eq!(grid_error(&extra), (9, 1, ChessError::TABLE_SIZE));
+    }
+
+    #[test]
+    fn test_parse_grid_file_labels_between_rows() {
+        let table = format!(
+            "{}\na b c d e f g h\n{}",
+            EMPTY_ROW,
+            [EMPTY_ROW; 7].join("\n")
+        );
+
+        assert_eq!(grid_error(&table), (2, 1, ChessError::FILE_LABEL));
     }
 }
@@ -0,0 +1,141 @@
+//! Properties of the pieces' geometry, checked over generated pairs of squares of the whole board.
+//! The hand-written cases live next to each piece; these make sure the rules hold everywhere.
+
+use chess::color::Color;
+use chess::piece_mod::piece::Piece;
+use chess::piece_type_mod::piece_kind::PieceKind;
+use chess::position_mod::position::Position;
+use proptest::prelude::*;
+
+/// Pieces whose moves look the same from both sides and from every corner of the board.
+const SYMMETRIC: [PieceKind; 5] = [
+    PieceKind::King,
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+];
+
+/// The eight ways of turning or flipping the board onto itself.
+#[derive(Debug, Clone, Copy)]
+enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    MirrorFiles,
+    MirrorRanks,
+    MainDiagonal,
+    AntiDiagonal,
+}
+
+impl Symmetry {
+    const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::MirrorFiles,
+        Symmetry::MirrorRanks,
+        Symmetry::MainDiagonal,
+        Symmetry::AntiDiagonal,
+    ];
+
+    fn apply(&self, square: Position) -> Position {
+        let Position { x, y } = square;
+        let (x, y) = match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::Rotate90 => (y, 7 - x),
+            Symmetry::Rotate180 => (7 - x, 7 - y),
+            Symmetry::Rotate270 => (7 - y, x),
+            Symmetry::MirrorFiles => (7 - x, y),
+            Symmetry::MirrorRanks => (x, 7 - y),
+            Symmetry::MainDiagonal => (y, x),
+            Symmetry::AntiDiagonal => (7 - y, 7 - x),
+        };
+        Position { x, y }
+    }
+}
+
+fn square() -> impl Strategy<Value = Position> {
+    (0..8, 0..8).prop_map(|(x, y)| Position { x, y })
+}
+
+/// Two different squares of the board.
+fn squares() -> impl Strategy<Value = (Position, Position)> {
+    (square(), square()).prop_filter("squares must differ", |(from, to)| from != to)
+}
+
+fn color() -> impl Strategy<Value = Color> {
+    prop_oneof![Just(Color::White), Just(Color::Black)]
+}
+
+fn symmetric_kind() -> impl Strategy<Value = PieceKind> {
+    proptest::sample::select(SYMMETRIC.to_vec())
+}
+
+fn symmetry() -> impl Strategy<Value = Symmetry> {
+    proptest::sample::select(Symmetry::ALL.to_vec())
+}
+
+/// Returns true if a piece of the kind and color standing on `from` captures a rival on `to`.
+fn captures(kind: PieceKind, color: Color, from: Position, to: Position) -> bool {
+    let piece = Piece::from_kind(kind, color, from);
+    let rival = Piece::from_kind(PieceKind::Pawn, color.opposite(), to);
+    piece.can_capture(&rival).did_win
+}
+
+proptest! {
+    #[test]
+    fn test_attacks_are_symmetric(kind in symmetric_kind(), color in color(), (a, b) in squares()) {
+        prop_assert_eq!(captures(kind, color, a, b), captures(kind, color.opposite(), b, a));
+    }
+
+    #[test]
+    fn test_board_symmetries_preserve_attacks(
+        kind in symmetric_kind(),
+        color in color(),
+        symmetry in symmetry(),
+        (from, to) in squares(),
+    ) {
+        prop_assert_eq!(
+            captures(kind, color, from, to),
+            captures(kind, color, symmetry.apply(from), symmetry.apply(to))
+        );
+    }
+
+    #[test]
+    fn test_mirroring_files_preserves_pawn_attacks(color in color(), (from, to) in squares()) {
+        let mirror = Symmetry::MirrorFiles;
+        prop_assert_eq!(
+            captures(PieceKind::Pawn, color, from, to),
+            captures(PieceKind::Pawn, color, mirror.apply(from), mirror.apply(to))
+        );
+    }
+
+    #[test]
+    fn test_mirroring_colors_flips_pawn_attacks(color in color(), (from, to) in squares()) {
+        let mirror = Symmetry::MirrorRanks;
+        prop_assert_eq!(
+            captures(PieceKind::Pawn, color, from, to),
+            captures(PieceKind::Pawn, color.opposite(), mirror.apply(from), mirror.apply(to))
+        );
+    }
+
+    #[test]
+    fn test_pawns_only_capture_forward(color in color(), (from, to) in squares()) {
+        if captures(PieceKind::Pawn, color, from, to) {
+            prop_assert_eq!(to.y - from.y, color.pawn_direction());
+            prop_assert_eq!((to.x - from.x).abs(), 1);
+        }
+    }
+
+    #[test]
+    fn test_queen_attacks_like_rook_or_bishop(color in color(), (from, to) in squares()) {
+        prop_assert_eq!(
+            captures(PieceKind::Queen, color, from, to),
+            captures(PieceKind::Rook, color, from, to)
+                || captures(PieceKind::Bishop, color, from, to)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use fs2::FileExt;
+use std::cell::RefCell;
+use std::env::{self, consts::EXE_SUFFIX};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, PathBuf};
+use std::process;
+
+// The directory, next to info.toml, where exercises are compiled when there is no rustlings home.
+// It is kept out of the exercises tree, and is ignored by git like any target directory
+const LOCAL_BUILD_DIR: &str = "target/rustlings";
+
+// The prefix of the directories that each run of rustlings compiles its exercises in
+const RUN_DIR_PREFIX: &str = "run-";
+
+// The file in the directory of each run that the run keeps locked for as long as it lives.
+// The operating system releases the lock when the process exits, however that happens,
+// which tells other runs that the directory can go
+const LOCK_FILE: &str = "lock";
+
+// The directory where exercises are compiled, under `target` in the rustlings home.
+// Every pack gets its own, at the path of the pack, so that nothing is written into the pack
+// or into whichever directory rustlings is run from, and packs never share their builds
+pub fn build_dir() -> PathBuf {
+    let pack = env::current_dir().and_then(|dir| dir.canonicalize());
+    match (home(), pack) {
+        (Some(home), Ok(pack)) => {
+            let mut dir = home.join("target");
+            dir.extend(pack.components().filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            }));
+            dir
+        }
+        _ => PathBuf::from(LOCAL_BUILD_DIR),
+    }
+}
+
+#[cfg(not(test))]
+fn home() -> Option<PathBuf> {
+    crate::pack::rustlings_home()
+}
+
+// The tests build in the target directory of the crate, rather than in the home of their user
+#[cfg(test)]
+fn home() -> Option<PathBuf> {
+    Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/home"))
+}
+
+thread_local! {
+    // The lock of this run, which is started and ended on the main thread
+    static RUN_LOCK: RefCell<Option<File>> = RefCell::new(None);
+}
+
+// The directory where this run of rustlings puts the binaries of the exercises.
+// Every run gets its own, so that runs at the same time do not overwrite each other
+pub fn run_dir() -> PathBuf {
+    build_dir().join(format!("{RUN_DIR_PREFIX}{}", process::id()))
+}
+
+// Create the directory of this run, with its lock held in it.
+// The lock file is locked under a temporary name and then moved into the directory,
+// so that other runs never find it unlocked while this run is alive
+pub fn start_run() -> io::Result<()> {
+    let build_dir = build_dir();
+    fs::create_dir_all(&build_dir)?;
+    let temp_path = build_dir.join(format!(".{RUN_DIR_PREFIX}{}.{LOCK_FILE}", process::id()));
+    let lock = File::create(&temp_path)?;
+    lock.lock_exclusive()?;
+    let dir = run_dir();
+    fs::create_dir_all(&dir)?;
+    fs::rename(&temp_path, dir.join(LOCK_FILE))?;
+    RUN_LOCK.with(|run_lock| *run_lock.borrow_mut() = Some(lock));
+    Ok(())
+}
+
+// The path of a binary in the directory of this run, which is created if needed
+pub fn binary_path(name: &str) -> io::Result<PathBuf> {
+    let dir = run_dir();
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{name}{EXE_SUFFIX}")))
+}
+
+// The Cargo project in which the given Clippy exercise is linted.
+// Its target directory is shared by all the Clippy exercises and kept between runs,
+// so that their dependencies are only built once
+pub fn clippy_dir(name: &str) -> PathBuf {
+    build_dir().join("clippy").join(name)
+}
+
+pub fn clippy_target_dir() -> PathBuf {
+    build_dir().join("clippy-target")
+}
+
+// The target directory of the Cargo project exercises, kept between runs like the one of Clippy
+pub fn cargo_target_dir() -> PathBuf {
+    build_dir().join("cargo-target")
+}
+
+// Remove what earlier runs of rustlings left behind when they did not exit cleanly,
+// i.e. the directories whose lock is no longer held.
+// A directory without a lock file is left alone, as its run may not have moved it there yet
+pub fn clean_stale_runs() {
+    let entries = match fs::read_dir(build_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let pid = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(RUN_DIR_PREFIX))
+            .and_then(|pid| pid.parse::<u32>().ok());
+        if pid.is_none() || pid == Some(process::id()) {
+            continue;
+        }
+        let stale = match File::open(entry.path().join(LOCK_FILE)) {
+            Ok(lock) => lock.try_lock_exclusive().is_ok(),
+            Err(_) => false,
+        };
+        // The lock is released before the removal, as Windows does not remove open files
+        if stale {
+            let _ignored = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+// Remove the directory of this run, once it no longer needs its binaries
+pub fn clean_run() {
+    let lock = RUN_LOCK.with(|run_lock| run_lock.borrow_mut().take());
+    drop(lock);
+    let _ignored = fs::remove_dir_all(run_dir());
+}
+
+// Exit with the given code, once the directory of this run is removed.
+// Every exit of a run goes through here, so that it does not leave its binaries behind
+pub fn exit(code: i32) -> ! {
+    clean_run();
+    process::exit(code)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binary_path() {
+        let path = binary_path("example").unwrap();
+        assert!(path.starts_with(build_dir()));
+        assert!(path.starts_with(home().unwrap().join("target")));
+        assert!(path.starts_with(run_dir()));
+        assert!(run_dir().is_dir());
+    }
+
+    #[test]
+    fn test_start_run() {
+        start_run().unwrap();
+        let lock = File::open(run_dir().join(LOCK_FILE)).unwrap();
+        assert!(lock.try_lock_exclusive().is_err());
+    }
+
+    #[test]
+    fn test_clean_stale_runs() {
+        let run = |pid: i32| build_dir().join(format!("{RUN_DIR_PREFIX}{pid}"));
+        // No process can have such a large pid, so its lock was released with its process
+        let stale = run(i32::MAX);
+        fs::create_dir_all(&stale).unwrap();
+        fs::write(stale.join("binary"), "").unwrap();
+        File::create(stale.join(LOCK_FILE)).unwrap();
+        // A run that still holds its lock is left alone
+        let running = run(i32::MAX - 1);
+        fs::create_dir_all(&running).unwrap();
+        let lock = File::create(running.join(LOCK_FILE)).unwrap();
+        lock.lock_exclusive().unwrap();
+        // And so is a run that has not moved its lock into its directory yet
+        let starting = run(i32::MAX - 2);
+        fs::create_dir_all(&starting).unwrap();
+        let current = binary_path("stale_runs").unwrap();
+        fs::write(&current, "").unwrap();
+
+        clean_stale_runs();
+        assert!(!stale.exists());
+        assert!(running.exists());
+        assert!(starting.exists());
+        assert!(current.exists());
+
+        drop(lock);
+        clean_stale_runs();
+        assert!(!running.exists());
+        fs::remove_dir_all(starting).unwrap();
+        fs::remove_file(current).unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+
+// A diagnostic, as printed by rustc with `--error-format json`
+#[derive(Deserialize, Debug)]
+pub struct Diagnostic {
+    pub message: String,
+    pub code: Option<DiagnosticCode>,
+    // "error", "warning", "note", ...
+    pub level: String,
+    pub spans: Vec<DiagnosticSpan>,
+    // The diagnostic as rustc would have printed it, with its spans and notes
+    pub rendered: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DiagnosticCode {
+    // An error code like E0308, or the name of a lint
+    pub code: String,
+}
+
+// A part of the source that the diagnostic points at
+#[derive(Deserialize, Debug)]
+pub struct DiagnosticSpan {
+    pub file_name: String,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub is_primary: bool,
+}
+
+// A line of JSON output: either a diagnostic from rustc,
+// or a message from cargo with `--message-format json`, which wraps the diagnostics of rustc
+// and also reports other events without a diagnostic, like the artifacts it built
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Message {
+    Cargo {
+        reason: String,
+        message: Option<Diagnostic>,
+    },
+    Rustc(Diagnostic),
+}
+
+impl Diagnostic {
+    fn is_error(&self) -> bool {
+        // The count of errors that rustc prints last is not an error of its own
+        self.level == "error" && !self.message.starts_with("aborting due to")
+    }
+
+    // The diagnostic as rustc would have printed it
+    fn text(&self) -> String {
+        match &self.rendered {
+            Some(rendered) => rendered.clone(),
+            None => format!("{}: {}", self.level, self.message),
+        }
+    }
+
+    // The message and where the diagnostic points, which tells apart errors with the same message
+    fn key(&self) -> (&str, Option<(&str, usize, usize)>) {
+        let location = self
+            .spans
+            .iter()
+            .find(|span| span.is_primary)
+            .map(|span| (span.file_name.as_str(), span.line_start, span.column_start));
+        (&self.message, location)
+    }
+}
+
+// Read a line of the JSON output of rustc or cargo.
+// Returns None for a line that is not JSON, like the ones cargo prints on stderr,
+// and Some(None) for a message of cargo that is not a diagnostic
+fn parse_line(line: &str) -> Option<Option<Diagnostic>> {
+    match serde_json::from_str::<Message>(line).ok()? {
+        Message::Cargo { reason, message } if reason == "compiler-message" => Some(message),
+        Message::Cargo { .. } => Some(None),
+        Message::Rustc(diagnostic) => Some(Some(diagnostic)),
+    }
+}
+
+// Read the diagnostics from the JSON output of rustc or cargo,
+// skipping the lines that are not diagnostics
+pub fn parse(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| parse_line(line)?)
+        .collect()
+}
+
+// Turn the JSON output of rustc or cargo back into text, with every diagnostic
+// as rustc would have printed it. Lines that are not JSON are kept as they are
+pub fn render(output: &str) -> String {
+    let mut text = String::new();
+    for line in output.lines() {
+        match parse_line(line) {
+            Some(Some(diagnostic)) => {
+                text += diagnostic.text().trim_end();
+                text.push('\n');
+            }
+            Some(None) => {}
+            None => {
+                text += line;
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+// Show only the first error, so that beginners fix one thing at a time.
+// Later errors are often caused by the first one, so they are only counted,
+// leaving out the duplicates that cargo reports once for each target.
+// Errors from rustc come with the command that explains them in detail.
+// Returns None when there is no error to show.
+pub fn focus(diagnostics: &[Diagnostic]) -> Option<String> {
+    let mut errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_error());
+    let first = errors.next()?;
+
+    let mut shown = first.text().trim_end().to_string();
+    if let Some(code) = first.code.as_ref().map(|code| &code.code) {
+        if code.starts_with('E') {
+            shown += &format!("\n\nRun `rustc --explain {code}` to learn more about this error.");
+        }
+    }
+
+    let mut seen = HashSet::from([first.key()]);
+    let hidden = errors.filter(|error| seen.insert(error.key())).count();
+    match hidden {
+        0 => {}
+        1 => shown += "\n\n1 more error is hidden, fix this one first.",
+        _ => shown += &format!("\n\n{hidden} more errors are hidden, fix this one first."),
+    }
+    Some(shown)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MISMATCHED: &str = r#"{"message":"mismatched types","code":{"code":"E0308","explanation":"..."},"level":"error","spans":[{"file_name":"example.rs","line_start":2,"column_start":18,"is_primary":true}],"rendered":"error[E0308]: mismatched types\n --> example.rs:2:18\n"}"#;
+    const NOT_FOUND: &str = r#"{"message":"cannot find function `foo` in this scope","code":{"code":"E0425","explanation":"..."},"level":"error","spans":[{"file_name":"example.rs","line_start":4,"column_start":5,"is_primary":true}],"rendered":"error[E0425]: cannot find function `foo` in this scope\n"}"#;
+    const ABORTING: &str = r#"{"message":"aborting due to 2 previous errors","code":null,"level":"error","spans":[],"rendered":"error: aborting due to 2 previous errors\n"}"#;
+
+    #[test]
+    fn test_focus_on_first_error() {
+        let output = [MISMATCHED, NOT_FOUND, ABORTING].join("\n");
+        let diagnostics = parse(&output);
+        assert_eq!(diagnostics.len(), 3);
+
+        let shown = focus(&diagnostics).unwrap();
+        assert!(shown.starts_with("error[E0308]: mismatched types\n --> example.rs:2:18"));
+        assert!(shown.contains("rustc --explain E0308"));
+        assert!(!shown.contains("E0425"));
+        assert!(shown.ends_with("1 more error is hidden, fix this one first."));
+    }
+
+    #[test]
+    fn test_duplicates_from_cargo() {
+        let output = format!(
+            "{}\n{}\n{}\nerror: could not compile",
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            format_args!(r#"{{"reason":"compiler-message","message":{MISMATCHED}}}"#),
+            format_args!(r#"{{"reason":"compiler-message","message":{MISMATCHED}}}"#),
+        );
+        let diagnostics = parse(&output);
+        assert_eq!(diagnostics.len(), 2);
+
+        let shown = focus(&diagnostics).unwrap();
+        assert!(!shown.contains("hidden"));
+    }
+
+    #[test]
+    fn test_render_everything() {
+        let output = format!(
+            "{}\n{}\n{}\nerror: could not compile",
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            format_args!(r#"{{"reason":"compiler-message","message":{MISMATCHED}}}"#),
+            format_args!(r#"{{"reason":"compiler-message","message":{NOT_FOUND}}}"#),
+        );
+
+        assert_eq!(
+            render(&output),
+            "error[E0308]: mismatched types\n --> example.rs:2:18\n\
+             error[E0425]: cannot find function `foo` in this scope\n\
+             error: could not compile\n"
+        );
+    }
+
+    #[test]
+    fn test_no_error() {
+        let warning = r#"{"message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[],"rendered":"warning: unused variable: `x`\n"}"#;
+        assert!(focus(&parse(warning)).is_none());
+        assert!(focus(&parse("error: linking with `cc` failed")).is_none());
+    }
+}
@@ -1,31 +1,49 @@
+use crate::build_dir;
+use crate::diagnostics::{self, Diagnostic};
+use crate::hint::Hints;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::ffi::OsStr;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
 const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
+// Diagnostics as JSON, each rendered with colors like rustc would print it.
+// rustc does not take `--color` along with them
+const RUSTC_JSON_ARGS: &[&str] = &[
+    "--error-format",
+    "json",
+    "--json",
+    "diagnostic-rendered-ansi",
+];
+const CARGO_JSON_ARGS: &[&str] = &["--message-format", "json-diagnostic-rendered-ansi"];
 const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
-
-// Get a temporary file name that is hopefully unique
-#[inline]
-fn temp_file() -> String {
-    let thread_id: String = format!("{:?}", std::thread::current().id())
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect();
-
-    format!("./temp_{}_{thread_id}", process::id())
+// Seconds an exercise binary may run when no timeout is set in info.toml
+const DEFAULT_TIMEOUT: u64 = 10;
+// How often a running exercise binary is checked for having exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// Get a temporary file name in the build directory that is unique to this compilation,
+// so that exercises compiled at the same time do not overwrite each other
+fn temp_file(name: &str) -> PathBuf {
+    static COMPILATIONS: AtomicUsize = AtomicUsize::new(0);
+    let compilation = COMPILATIONS.fetch_add(1, Ordering::Relaxed);
+
+    build_dir::binary_path(&format!("{name}_{compilation}"))
+        .expect("Failed to create the build directory")
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -34,25 +52,89 @@ pub enum Mode {
     Test,
     // Indicates that the exercise should be linted with clippy
     Clippy,
+    // Indicates that the exercise is a directory with its own Cargo.toml,
+    // which should be built and tested with cargo
+    Cargo,
 }
 
 #[derive(Deserialize)]
 pub struct ExerciseList {
     pub exercises: Vec<Exercise>,
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+// Settings that apply to every exercise.
+// This is deserialized from the optional [settings] table of info.toml
+#[derive(Deserialize, Debug)]
+pub struct Settings {
+    // Whether an exercise that passes `verify` also needs its
+    // `I AM NOT DONE` marker removed to count as done
+    #[serde(default = "default_require_marker")]
+    pub require_marker: bool,
+    // Limits for the exercises that do not set their own
+    #[serde(flatten)]
+    pub limits: Limits,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            require_marker: default_require_marker(),
+            limits: Limits::default(),
+        }
+    }
+}
+
+fn default_require_marker() -> bool {
+    true
+}
+
+// Limits on the process that runs an exercise binary.
+// They can be set for every exercise in the [settings] table of info.toml,
+// and for a single exercise in its own entry, which takes precedence
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub struct Limits {
+    // Seconds of wall-clock time before the binary is killed
+    pub timeout: Option<u64>,
+    // Megabytes of memory the binary may use (only enforced on Unix)
+    pub memory_limit: Option<u64>,
+    // Seconds of CPU time the binary may use (only enforced on Unix)
+    pub cpu_limit: Option<u64>,
+}
+
+impl Limits {
+    // Take the limits that are not set here from the given ones
+    pub fn or(self, fallback: Limits) -> Limits {
+        Limits {
+            timeout: self.timeout.or(fallback.timeout),
+            memory_limit: self.memory_limit.or(fallback.memory_limit),
+            cpu_limit: self.cpu_limit.or(fallback.cpu_limit),
+        }
+    }
+
+    // The wall-clock time the binary may run for
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+    }
 }
 
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
-    // The path to the file containing the exercise's source code
+    // The path to the file containing the exercise's source code,
+    // or to the directory of its Cargo project
     pub path: PathBuf,
-    // The mode of the exercise (Test, Compile, or Clippy)
+    // The mode of the exercise (Test, Compile, Clippy, or Cargo)
     pub mode: Mode,
-    // The hint text associated with the exercise
-    pub hint: String,
+    // The hints associated with the exercise
+    pub hint: Hints,
+    // The limits on running the exercise binary
+    #[serde(flatten)]
+    pub limits: Limits,
 }
 
 // An enum to track of the state of an Exercise.
@@ -79,13 +161,18 @@ pub struct ContextLine {
 // The result of compiling an exercise
 pub struct CompiledExercise<'a> {
     exercise: &'a Exercise,
-    _handle: FileHandle,
+    binary: FileHandle,
+    // The test binaries that cargo built for a Cargo project exercise
+    tests: Vec<PathBuf>,
 }
 
 impl<'a> CompiledExercise<'a> {
     // Run the compiled exercise
-    pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+    pub fn run(&self) -> Result<ExerciseOutput, RunError> {
+        match self.exercise.mode {
+            Mode::Cargo => self.exercise.run_tests(&self.tests),
+            _ => self.exercise.run(&self.binary.0),
+        }
     }
 }
 
@@ -98,28 +185,70 @@ pub struct ExerciseOutput {
     pub stderr: String,
 }
 
-struct FileHandle;
+// The reason an exercise did not compile
+#[derive(Debug)]
+pub struct CompileError {
+    // The output of the compiler, with every diagnostic as rustc would have printed it
+    pub output: ExerciseOutput,
+    // The diagnostics of the compiler, to tell which error to show first
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompileError {
+    // Only the first error of the compiler, or its whole output when there is no error
+    // among the diagnostics, such as when cargo fails before compiling
+    pub fn focused(&self) -> String {
+        diagnostics::focus(&self.diagnostics).unwrap_or_else(|| self.output.stderr.clone())
+    }
+}
+
+// The reason an exercise binary did not run successfully
+#[derive(Debug)]
+pub enum RunError {
+    // The binary exited with a failure, e.g. a panic or a failing test
+    Failed(ExerciseOutput),
+    // The binary was killed after running for longer than the given time
+    TimedOut(Duration, ExerciseOutput),
+}
+
+impl RunError {
+    fn output_mut(&mut self) -> &mut ExerciseOutput {
+        match self {
+            RunError::Failed(output) | RunError::TimedOut(_, output) => output,
+        }
+    }
+}
+
+// A temporary file that is removed when the handle is dropped
+struct FileHandle(PathBuf);
 
 impl Drop for FileHandle {
     fn drop(&mut self) {
-        clean();
+        let _ignored = remove_file(&self.0);
     }
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, CompileError> {
+        let binary = FileHandle(temp_file(&self.name));
         let cmd = match self.mode {
             Mode::Compile => Command::new("rustc")
-                .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args([self.path.to_str().unwrap(), "-o"])
+                .arg(&binary.0)
                 .args(RUSTC_EDITION_ARGS)
+                .args(RUSTC_JSON_ARGS)
                 .output(),
             Mode::Test => Command::new("rustc")
-                .args(&["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args(["--test", self.path.to_str().unwrap(), "-o"])
+                .arg(&binary.0)
                 .args(RUSTC_EDITION_ARGS)
+                .args(RUSTC_JSON_ARGS)
                 .output(),
             Mode::Clippy => {
+                // Lint the exercise in a Cargo project of its own in the build directory,
+                // rather than writing a Cargo.toml into the exercises
+                let project = build_dir::clippy_dir(&self.name);
+                let source = fs::canonicalize(&self.path).expect("Failed to find the exercise");
                 let cargo_toml = format!(
                     r#"[package]
 name = "{}"
@@ -127,126 +256,271 @@ version = "0.0.1"
 edition = "2021"
 [[bin]]
 name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
+path = {:?}
+[workspace]"#,
+                    self.name, self.name, source
                 );
                 let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
                     "Failed to write Clippy Cargo.toml file."
                 } else {
                     "Failed to write 📎 Clippy 📎 Cargo.toml file."
                 };
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
+                let manifest = project.join("Cargo.toml");
+                fs::create_dir_all(&project)
+                    .and_then(|_| fs::write(&manifest, cargo_toml))
+                    .expect(cargo_toml_error_msg);
                 // To support the ability to run the clippy exercises, build
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
                 Command::new("rustc")
-                    .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
+                    .args([self.path.to_str().unwrap(), "-o"])
+                    .arg(&binary.0)
                     .args(RUSTC_COLOR_ARGS)
                     .args(RUSTC_EDITION_ARGS)
                     .output()
                     .expect("Failed to compile!");
-                // Due to an issue with Clippy, a cargo clean is required to catch all lints.
-                // See https://github.com/rust-lang/rust-clippy/issues/2604
-                // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
-                // https://github.com/rust-lang/rust-clippy/issues/3837
-                Command::new("cargo")
-                    .args(&["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to run 'cargo clean'");
                 Command::new("cargo")
-                    .args(&["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
+                    .arg("clippy")
+                    .arg("--manifest-path")
+                    .arg(&manifest)
+                    .arg("--target-dir")
+                    .arg(build_dir::clippy_target_dir())
                     .args(RUSTC_COLOR_ARGS)
-                    .args(&["--", "-D", "warnings", "-D", "clippy::float_cmp"])
+                    .args(CARGO_JSON_ARGS)
+                    .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
                     .output()
             }
+            Mode::Cargo => self
+                .cargo("build")
+                .arg("--all-targets")
+                .args(RUSTC_COLOR_ARGS)
+                .args(CARGO_JSON_ARGS)
+                .output(),
         }
         .expect("Failed to run 'compile' command.");
 
         if cmd.status.success() {
+            let tests = match self.mode {
+                Mode::Cargo => self.test_binaries(),
+                _ => Vec::new(),
+            };
             Ok(CompiledExercise {
                 exercise: self,
-                _handle: FileHandle,
+                binary,
+                tests,
             })
         } else {
-            clean();
-            Err(ExerciseOutput {
-                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            let stdout = String::from_utf8_lossy(&cmd.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&cmd.stderr).to_string();
+            // rustc prints its diagnostics on stderr, and cargo on stdout.
+            // Either way, they end up on stderr, where they would be without JSON
+            let (diagnostics, stderr) = match self.mode {
+                Mode::Compile | Mode::Test => {
+                    (diagnostics::parse(&stderr), diagnostics::render(&stderr))
+                }
+                Mode::Clippy | Mode::Cargo => (
+                    diagnostics::parse(&stdout),
+                    diagnostics::render(&stdout) + &stderr,
+                ),
+            };
+            Err(CompileError {
+                output: ExerciseOutput {
+                    stdout: String::new(),
+                    stderr,
+                },
+                diagnostics,
             })
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
+    fn run(&self, binary: &Path) -> Result<ExerciseOutput, RunError> {
         let arg = match self.mode {
             Mode::Test => "--show-output",
             _ => "",
         };
-        let cmd = Command::new(&temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
-
-        let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
-        };
+        let mut command = Command::new(binary);
+        command.arg(arg);
+        self.run_command(command)
+    }
 
-        if cmd.status.success() {
-            Ok(output)
-        } else {
-            Err(output)
+    // Run the test binaries of a Cargo project exercise one after another,
+    // from the directory of the project like `cargo test` does
+    fn run_tests(&self, tests: &[PathBuf]) -> Result<ExerciseOutput, RunError> {
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        for test in tests {
+            let mut command = Command::new(test);
+            command.arg("--show-output").current_dir(&self.path);
+            match self.run_command(command) {
+                Ok(output) => {
+                    stdout += &output.stdout;
+                    stderr += &output.stderr;
+                }
+                Err(mut error) => {
+                    let output = error.output_mut();
+                    output.stdout.insert_str(0, &stdout);
+                    output.stderr.insert_str(0, &stderr);
+                    return Err(error);
+                }
+            }
         }
+        Ok(ExerciseOutput { stdout, stderr })
     }
 
-    pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
+    // Run the given command within the limits of the exercise
+    fn run_command(&self, mut command: Command) -> Result<ExerciseOutput, RunError> {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        limit_resources(&mut command, &self.limits);
+        let mut child = command.spawn().expect("Failed to run 'run' command");
+
+        // Read the output while the binary runs, so that it does not
+        // block on a full pipe before we get to wait for it
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
+
+        let timeout = self.limits.timeout();
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().expect("Failed to wait for 'run' command") {
+                break Some(status);
+            }
+            if started.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            thread::sleep(POLL_INTERVAL);
         };
 
-        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+        let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+            let bytes = reader
+                .map(|reader| reader.join().unwrap_or_default())
+                .unwrap_or_default();
+            String::from_utf8_lossy(&bytes).to_string()
+        };
+        let output = ExerciseOutput {
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        };
 
-        if !re.is_match(&source) {
-            return State::Done;
+        match status {
+            Some(status) if status.success() => Ok(output),
+            Some(status) => match self.cpu_time_exceeded(status) {
+                Some(limit) => Err(RunError::TimedOut(limit, output)),
+                None => Err(RunError::Failed(output)),
+            },
+            None => Err(RunError::TimedOut(timeout, output)),
         }
+    }
 
-        let matched_line_index = source
-            .lines()
-            .enumerate()
-            .find_map(|(i, line)| if re.is_match(line) { Some(i) } else { None })
-            .expect("This should not happen at all");
+    // A cargo command on the Cargo project of the exercise, which runs offline
+    // and keeps its build artifacts in the build directory
+    fn cargo(&self, subcommand: &str) -> Command {
+        let mut command = Command::new("cargo");
+        command
+            .args([subcommand, "--offline", "--manifest-path"])
+            .arg(self.path.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(build_dir::cargo_target_dir());
+        command
+    }
 
-        let min_line = ((matched_line_index as i32) - (CONTEXT as i32)).max(0) as usize;
-        let max_line = matched_line_index + CONTEXT;
+    // Ask cargo for the test binaries it built for the Cargo project exercise.
+    // Running them directly, rather than through `cargo test`, lets the
+    // limits of the exercise apply to them
+    fn test_binaries(&self) -> Vec<PathBuf> {
+        let output = self
+            .cargo("test")
+            .args(["--no-run", "--message-format", "json"])
+            .stderr(Stdio::null())
+            .output()
+            .expect("Failed to run 'cargo test'");
 
-        let context = source
+        String::from_utf8_lossy(&output.stdout)
             .lines()
-            .enumerate()
-            .filter(|&(i, _)| i >= min_line && i <= max_line)
-            .map(|(i, line)| ContextLine {
-                line: line.to_string(),
-                number: i + 1,
-                important: i == matched_line_index,
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|message| {
+                message["reason"] == "compiler-artifact" && message["profile"]["test"] == true
             })
-            .collect();
+            .filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+            .collect()
+    }
+
+    // The files that make up the exercise: its source file, or the files of
+    // its Cargo project, apart from the ones cargo generates
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        if let Mode::Cargo = self.mode {
+            collect_files(&self.path, &mut files)?;
+            files.sort();
+        } else {
+            files.push(self.path.clone());
+        }
+        Ok(files)
+    }
+
+    // Check whether the given file belongs to the exercise
+    pub fn contains(&self, file: &Path) -> bool {
+        match self.mode {
+            Mode::Cargo => match self.path.canonicalize() {
+                Ok(dir) => file.starts_with(dir),
+                Err(_) => false,
+            },
+            _ => file.ends_with(&self.path),
+        }
+    }
 
-        State::Pending(context)
+    // The CPU time limit, if the binary was killed for going over it
+    #[cfg(unix)]
+    fn cpu_time_exceeded(&self, status: ExitStatus) -> Option<Duration> {
+        use std::os::unix::process::ExitStatusExt;
+        match (self.limits.cpu_limit, status.signal()) {
+            (Some(seconds), Some(libc::SIGXCPU | libc::SIGKILL)) => {
+                Some(Duration::from_secs(seconds))
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn cpu_time_exceeded(&self, _status: ExitStatus) -> Option<Duration> {
+        None
+    }
+
+    pub fn state(&self) -> State {
+        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+        let files = self
+            .files()
+            .expect("We were unable to list the exercise files!");
+
+        for path in files {
+            if path.extension() != Some(OsStr::new("rs")) {
+                continue;
+            }
+            let mut source_file =
+                File::open(&path).expect("We were unable to open the exercise file!");
+
+            let source = {
+                let mut s = String::new();
+                source_file
+                    .read_to_string(&mut s)
+                    .expect("We were unable to read the exercise file!");
+                s
+            };
+
+            if re.is_match(&source) {
+                return State::Pending(pending_context(&re, &source));
+            }
+        }
+
+        State::Done
     }
 
     // Check that the exercise looks to be solved using self.state()
-    // This is not the best way to check since
-    // the user can just remove the "I AM NOT DONE" string from the file
-    // without actually having solved anything.
-    // The only other way to truly check this would to compile and run
-    // the exercise; which would be both costly and counterintuitive
+    // This only tells whether the "I AM NOT DONE" marker was removed,
+    // which the user can do without actually having solved anything.
+    // Whether the exercise really passed is recorded by `verify`
+    // in the progress file, see `Progress::is_done`
     pub fn looks_done(&self) -> bool {
         self.state() == State::Done
     }
@@ -258,28 +532,125 @@ impl Display for Exercise {
     }
 }
 
-#[inline]
-fn clean() {
-    let _ignored = remove_file(&temp_file());
+// The lines around the first `I AM NOT DONE` marker of the given source
+fn pending_context(re: &Regex, source: &str) -> Vec<ContextLine> {
+    let matched_line_index = source
+        .lines()
+        .enumerate()
+        .find_map(|(i, line)| if re.is_match(line) { Some(i) } else { None })
+        .expect("This should not happen at all");
+
+    let min_line = ((matched_line_index as i32) - (CONTEXT as i32)).max(0) as usize;
+    let max_line = matched_line_index + CONTEXT;
+
+    source
+        .lines()
+        .enumerate()
+        .filter(|&(i, _)| i >= min_line && i <= max_line)
+        .map(|(i, line)| ContextLine {
+            line: line.to_string(),
+            number: i + 1,
+            important: i == matched_line_index,
+        })
+        .collect()
+}
+
+// Collect the files of the directory and its subdirectories,
+// leaving out the build artifacts and lock file that cargo generates
+pub fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                collect_files(&path, files)?;
+            }
+        } else if !path.ends_with("Cargo.lock") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Read everything from the given pipe in another thread
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        bytes
+    })
 }
 
+// Apply the memory and CPU limits to the process of the exercise binary
+#[cfg(unix)]
+fn limit_resources(command: &mut Command, limits: &Limits) {
+    use std::os::unix::process::CommandExt;
+
+    let memory = limits.memory_limit.map(|megabytes| megabytes * 1024 * 1024);
+    let cpu = limits.cpu_limit;
+    if memory.is_none() && cpu.is_none() {
+        return;
+    }
+    let rlimit = |soft: u64, hard: u64| libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+    // SAFETY: the closure runs in the child between fork and exec,
+    // where it only calls setrlimit, which is async-signal-safe
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(bytes) = memory {
+                if libc::setrlimit(libc::RLIMIT_AS, &rlimit(bytes, bytes)) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            // The binary gets SIGXCPU at the soft limit, and SIGKILL one second later
+            if let Some(seconds) = cpu {
+                if libc::setrlimit(libc::RLIMIT_CPU, &rlimit(seconds, seconds + 1)) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_resources(_command: &mut Command, _limits: &Limits) {}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn test_clean() {
-        File::create(&temp_file()).unwrap();
         let exercise = Exercise {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::from(""),
+            hint: Hints::default(),
+            limits: Limits::default(),
         };
         let compiled = exercise.compile().unwrap();
+        let binary = compiled.binary.0.clone();
+        assert!(binary.exists());
         drop(compiled);
-        assert!(!Path::new(&temp_file()).exists());
+        assert!(!binary.exists());
+    }
+
+    #[test]
+    fn test_compilations_do_not_share_binaries() {
+        let exercise = Exercise {
+            name: "example".into(),
+            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
+            mode: Mode::Compile,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        };
+        let first = exercise.compile().unwrap();
+        let second = exercise.compile().unwrap();
+        assert_ne!(first.binary.0, second.binary.0);
+        drop(first);
+        assert!(second.run().is_ok());
     }
 
     #[test]
@@ -288,7 +659,8 @@ mod test {
             name: "pending_exercise".into(),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            hint: Hints::default(),
+            limits: Limits::default(),
         };
 
         let state = exercise.state();
@@ -329,19 +701,133 @@ mod test {
             name: "finished_exercise".into(),
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            hint: Hints::default(),
+            limits: Limits::default(),
         };
 
         assert_eq!(exercise.state(), State::Done);
     }
 
+    #[test]
+    fn test_pending_cargo_exercise() {
+        let exercise = Exercise {
+            name: "pending_cargo_exercise".into(),
+            path: PathBuf::from("tests/fixture/state/pending_cargo_exercise"),
+            mode: Mode::Cargo,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        };
+
+        let files = exercise.files().unwrap();
+        assert!(files.contains(&exercise.path.join("Cargo.toml")));
+        assert!(files.contains(&exercise.path.join("src").join("greeting.rs")));
+        assert!(exercise.contains(&exercise.path.join("src/main.rs").canonicalize().unwrap()));
+
+        match exercise.state() {
+            State::Pending(context) => {
+                assert!(context
+                    .iter()
+                    .any(|line| line.important && line.line == "// I AM NOT DONE"));
+            }
+            State::Done => panic!("The marker in src/greeting.rs was not found"),
+        }
+    }
+
+    #[test]
+    fn test_cargo_exercise() {
+        let exercise = Exercise {
+            name: "cargoSuccess".into(),
+            path: PathBuf::from("tests/fixture/success/cargoSuccess"),
+            mode: Mode::Cargo,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        };
+        let compiled = exercise.compile().unwrap();
+        assert_eq!(compiled.tests.len(), 3);
+        let out = compiled.run().unwrap();
+        assert!(out.stdout.contains("THE CRATE TESTS PASS"));
+        assert!(!exercise
+            .files()
+            .unwrap()
+            .iter()
+            .any(|file| file.ends_with("Cargo.lock")));
+    }
+
+    #[test]
+    fn test_settings() {
+        let exercises = r#"
+            [[exercises]]
+            name = "intro1"
+            path = "exercises/intro/intro1.rs"
+            mode = "compile"
+            hint = ""
+        "#;
+        let list = toml::from_str::<ExerciseList>(exercises).unwrap();
+        assert!(list.settings.require_marker);
+
+        let without_marker = format!("{exercises}\n[settings]\nrequire_marker = false\n");
+        let list = toml::from_str::<ExerciseList>(&without_marker).unwrap();
+        assert!(!list.settings.require_marker);
+    }
+
+    #[test]
+    fn test_limits() {
+        let exercises = r#"
+            [[exercises]]
+            name = "threads1"
+            path = "exercises/threads/threads1.rs"
+            mode = "compile"
+            hint = ""
+            timeout = 2
+
+            [settings]
+            timeout = 5
+            memory_limit = 256
+        "#;
+        let list = toml::from_str::<ExerciseList>(exercises).unwrap();
+        let limits = list.exercises[0].limits.or(list.settings.limits);
+
+        assert_eq!(
+            limits,
+            Limits {
+                timeout: Some(2),
+                memory_limit: Some(256),
+                cpu_limit: None,
+            }
+        );
+        assert_eq!(limits.timeout(), Duration::from_secs(2));
+        assert_eq!(
+            Limits::default().timeout(),
+            Duration::from_secs(DEFAULT_TIMEOUT)
+        );
+    }
+
+    #[test]
+    fn test_timed_out_exercise() {
+        let exercise = Exercise {
+            name: "compTimeout".into(),
+            path: PathBuf::from("tests/fixture/failure/compTimeout.rs"),
+            mode: Mode::Compile,
+            hint: Hints::default(),
+            limits: Limits {
+                timeout: Some(1),
+                ..Limits::default()
+            },
+        };
+        let result = exercise.compile().unwrap().run();
+        assert!(
+            matches!(result, Err(RunError::TimedOut(limit, _)) if limit == Duration::from_secs(1))
+        );
+    }
+
     #[test]
     fn test_exercise_with_output() {
         let exercise = Exercise {
             name: "exercise_with_output".into(),
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
-            hint: String::new(),
+            hint: Hints::default(),
+            limits: Limits::default(),
         };
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
@@ -0,0 +1,179 @@
+use crate::state_file::StateFile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+// The file, next to info.toml, where the number of hints revealed for each exercise is recorded
+pub const HINTS_FILE: &str = ".rustlings-hints.json";
+
+// The hints of an exercise, from the most general to the one that gives the most away.
+// In info.toml, `hint` is either a single string, or a list of strings
+#[derive(Deserialize, Clone, PartialEq, Default, Debug)]
+#[serde(from = "HintField")]
+pub struct Hints(Vec<String>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HintField {
+    Single(String),
+    Levels(Vec<String>),
+}
+
+impl From<HintField> for Hints {
+    fn from(field: HintField) -> Self {
+        match field {
+            HintField::Single(hint) => Hints(vec![hint]),
+            HintField::Levels(levels) => Hints(levels),
+        }
+    }
+}
+
+impl Hints {
+    // The number of levels of hints
+    pub fn levels(&self) -> usize {
+        self.0.len()
+    }
+
+    // Print the first `revealed` levels.
+    // A single hint is printed as is, like before hints had levels
+    pub fn print(&self, revealed: usize) {
+        if self.levels() == 1 {
+            println!("{}", self.0[0]);
+            return;
+        }
+        for (level, hint) in self.0.iter().enumerate().take(revealed) {
+            if level > 0 {
+                println!();
+            }
+            println!("Hint {}/{}:", level + 1, self.levels());
+            println!("{hint}");
+        }
+    }
+}
+
+// How many levels of hints were revealed for each exercise
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct RevealedHints {
+    // Name of each exercise with revealed hints and the number of levels revealed
+    hints: BTreeMap<String, usize>,
+    // Where the revealed hints are saved
+    #[serde(skip)]
+    file: StateFile,
+}
+
+impl RevealedHints {
+    // Load the revealed hints saved in the given file.
+    // A missing file means that no hint has been revealed yet.
+    // A file that can't be read or parsed is left alone, and the hints revealed
+    // until it is fixed are not saved
+    pub fn load(path: impl AsRef<Path>) -> RevealedHints {
+        let (file, mut revealed) = StateFile::load::<RevealedHints>(path);
+        revealed.file = file;
+        revealed
+    }
+
+    fn save(&self) -> io::Result<()> {
+        self.file.save(self)
+    }
+
+    // The number of levels revealed for the exercise, which is at least the first one
+    pub fn revealed(&self, name: &str) -> usize {
+        self.hints.get(name).copied().unwrap_or(0).max(1)
+    }
+
+    // Reveal the next level of the hints of the exercise, if there is one.
+    // Returns the number of levels revealed, including the new one.
+    // A single hint is always revealed, so nothing is saved for it
+    pub fn reveal_next(&mut self, name: &str, hints: &Hints) -> io::Result<usize> {
+        if hints.levels() <= 1 {
+            return Ok(hints.levels());
+        }
+        let revealed = match self.hints.get(name) {
+            Some(revealed) => (revealed + 1).min(hints.levels()),
+            None => 1,
+        };
+        self.hints.insert(name.to_string(), revealed);
+        self.save().map(|_| revealed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[derive(Deserialize)]
+    struct Exercise {
+        hint: Hints,
+    }
+
+    fn temp_hints_file(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!(
+            "rustlings_hints_{}_{name}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_single_hint() {
+        let exercise: Exercise = toml::from_str(r#"hint = "Hello!""#).unwrap();
+        assert_eq!(exercise.hint, Hints(vec!["Hello!".to_string()]));
+        assert_eq!(exercise.hint.levels(), 1);
+    }
+
+    #[test]
+    fn test_hint_levels() {
+        let exercise: Exercise =
+            toml::from_str(r#"hint = ["Look at line 3", "Add a `;`"]"#).unwrap();
+        assert_eq!(exercise.hint.levels(), 2);
+    }
+
+    #[test]
+    fn test_reveal_next() {
+        let path = temp_hints_file("reveal");
+        let hints = Hints(vec!["one".into(), "two".into(), "three".into()]);
+
+        let mut revealed = RevealedHints::load(&path);
+        assert_eq!(revealed.revealed("example"), 1);
+        assert_eq!(revealed.reveal_next("example", &hints).unwrap(), 1);
+        assert_eq!(revealed.reveal_next("example", &hints).unwrap(), 2);
+
+        let mut reloaded = RevealedHints::load(&path);
+        assert_eq!(reloaded.revealed("example"), 2);
+        assert_eq!(reloaded.reveal_next("example", &hints).unwrap(), 3);
+        assert_eq!(reloaded.reveal_next("example", &hints).unwrap(), 3);
+        assert_eq!(reloaded.revealed("other"), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_single_hint_is_not_saved() {
+        let path = temp_hints_file("single");
+        let mut revealed = RevealedHints::load(&path);
+
+        assert_eq!(
+            revealed
+                .reveal_next("example", &Hints(vec!["Hello!".to_string()]))
+                .unwrap(),
+            1
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_unreadable_file_is_not_overwritten() {
+        let path = temp_hints_file("broken");
+        fs::write(&path, "not json").unwrap();
+        let hints = Hints(vec!["one".into(), "two".into()]);
+
+        let mut revealed = RevealedHints::load(&path);
+        assert!(revealed.reveal_next("example", &hints).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+        fs::remove_file(&path).unwrap();
+    }
+}
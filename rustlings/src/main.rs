@@ -1,5 +1,10 @@
 use crate::exercise::{Exercise, ExerciseList};
+use crate::hint::{Hints, RevealedHints, HINTS_FILE};
+use crate::pack::{rustlings_home, Packs, PACKS_FILE};
+use crate::pristine::{Pristine, BACKUP_DIR, GIVEN_FILE, PRISTINE_DIR};
+use crate::progress::{Progress, PROGRESS_FILE};
 use crate::project::RustAnalyzerProject;
+use crate::report::{print_json, Format, ListEntry};
 use crate::run::{reset, run};
 use crate::verify::verify;
 use argh::FromArgs;
@@ -9,7 +14,7 @@ use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, prelude::*};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, RecvTimeoutError};
@@ -20,9 +25,17 @@ use std::time::Duration;
 #[macro_use]
 mod ui;
 
+mod build_dir;
+mod diagnostics;
 mod exercise;
+mod hint;
+mod pack;
+mod pristine;
+mod progress;
 mod project;
+mod report;
 mod run;
+mod state_file;
 mod verify;
 
 // In sync with crate version
@@ -51,12 +64,20 @@ enum Subcommands {
     Hint(HintArgs),
     List(ListArgs),
     Lsp(LspArgs),
+    Pack(PackArgs),
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "verify")]
 /// Verifies all exercises according to the recommended order
-struct VerifyArgs {}
+struct VerifyArgs {
+    #[argh(option, short = 'j', default = "1")]
+    /// how many exercises to compile and run at the same time
+    jobs: usize,
+    #[argh(option, default = "Format::Text")]
+    /// how to print the results: text (default) or json
+    format: Format,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "watch")]
@@ -70,20 +91,29 @@ struct RunArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(option, default = "Format::Text")]
+    /// how to print the result: text (default) or json
+    format: Format,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "reset")]
-/// Resets a single exercise using "git stash -- <filename>"
+/// Resets a single exercise, or all of them, to how they were given
 struct ResetArgs {
     #[argh(positional)]
     /// the name of the exercise
-    name: String,
+    name: Option<String>,
+    #[argh(switch, short = 'a')]
+    /// reset all the exercises
+    all: bool,
+    #[argh(switch, short = 'b')]
+    /// keep a copy of the current attempt in .rustlings-backup
+    backup: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "hint")]
-/// Returns a hint for the given exercise
+/// Returns a hint for the given exercise, revealing its next level on every call
 struct HintArgs {
     #[argh(positional)]
     /// the name of the exercise
@@ -95,6 +125,48 @@ struct HintArgs {
 /// Enable rust-analyzer for exercises
 struct LspArgs {}
 
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "pack")]
+/// Manages the exercise packs, which can live outside the rustlings directory
+struct PackArgs {
+    #[argh(subcommand)]
+    nested: PackSubcommands,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+enum PackSubcommands {
+    List(PackListArgs),
+    Use(PackUseArgs),
+    Add(PackAddArgs),
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "list")]
+/// Lists the installed packs
+struct PackListArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "use")]
+/// Uses the given pack when rustlings is run outside a directory with an info.toml
+struct PackUseArgs {
+    #[argh(positional)]
+    /// the name of the pack
+    name: String,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "add")]
+/// Installs the pack in the given directory, which must have an info.toml
+struct PackAddArgs {
+    #[argh(positional)]
+    /// the directory of the pack
+    path: PathBuf,
+    #[argh(option, short = 'n')]
+    /// the name of the pack, the name of its directory by default
+    name: Option<String>,
+}
+
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "list")]
 /// Lists the exercises available in Rustlings
@@ -115,6 +187,9 @@ struct ListArgs {
     #[argh(switch, short = 's')]
     /// display only exercises that have been solved
     solved: bool,
+    #[argh(option, default = "Format::Text")]
+    /// how to print the exercises: text (default) or json
+    format: Format,
 }
 
 fn main() {
@@ -129,13 +204,42 @@ fn main() {
         println!("\n{WELCOME}\n");
     }
 
-    if !Path::new("info.toml").exists() {
-        println!(
-            "{} must be run from the rustlings directory",
-            std::env::current_exe().unwrap().to_str().unwrap()
+    let packs = rustlings_home().map(|home| Packs::load(home.join(PACKS_FILE)));
+    if packs.as_ref().map_or(false, Packs::is_unreadable) {
+        eprintln!(
+            "Could not read your packs from {PACKS_FILE}, so they will not be saved until the file is fixed or removed."
         );
-        println!("Try `cd rustlings/`!");
-        std::process::exit(1);
+    }
+
+    if let Some(Subcommands::Pack(subargs)) = &args.nested {
+        let mut packs = match packs {
+            Some(packs) => packs,
+            None => {
+                println!("Could not find your home directory to keep the packs in.");
+                println!("Set RUSTLINGS_HOME to the directory where they should be kept.");
+                std::process::exit(1);
+            }
+        };
+        manage_packs(&subargs.nested, &mut packs).unwrap_or_else(|e| {
+            println!("{e}");
+            std::process::exit(1);
+        });
+        std::process::exit(0);
+    }
+
+    // Outside a directory with an info.toml, run the current pack from its own directory,
+    // which is also where its progress is kept
+    let current_pack = packs.as_ref().and_then(|packs| packs.current());
+    if !Path::new("info.toml").exists() {
+        let moved = current_pack.map(|(_, dir)| std::env::set_current_dir(dir).is_ok());
+        if moved != Some(true) {
+            println!(
+                "{} must be run from the rustlings directory",
+                std::env::current_exe().unwrap().to_str().unwrap()
+            );
+            println!("Try `cd rustlings/`, or choose a pack with `rustlings pack use`!");
+            std::process::exit(1);
+        }
     }
 
     if !rustc_exists() {
@@ -145,17 +249,38 @@ fn main() {
         std::process::exit(1);
     }
 
+    // Binaries of earlier runs that did not exit cleanly are not needed anymore.
+    // Without a lock, this run can still build, but others may clean up after it early
+    let _ignored = build_dir::start_run();
+    build_dir::clean_stale_runs();
+
     let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
+    let exercise_list = toml::from_str::<ExerciseList>(toml_str).unwrap();
+    let mut exercises = exercise_list.exercises;
+    for exercise in exercises.iter_mut() {
+        exercise.limits = exercise.limits.or(exercise_list.settings.limits);
+    }
+    let mut progress = Progress::load(PROGRESS_FILE, exercise_list.settings.require_marker);
+    if progress.is_unreadable() {
+        eprintln!(
+            "Could not read your progress from {PROGRESS_FILE}, so it will not be saved until the file is fixed or removed."
+        );
+    }
+    // Keep a copy of the exercises that are as the pack gives them, so that `reset`
+    // can bring them back. A failure only leaves `reset` without them
+    let pristine = Pristine::load(PRISTINE_DIR, GIVEN_FILE);
+    let _ignored = pristine.keep(&exercises);
     let verbose = args.nocapture;
 
     let command = args.nested.unwrap_or_else(|| {
         println!("{DEFAULT_OUT}\n");
-        std::process::exit(0);
+        build_dir::exit(0);
     });
     match command {
         Subcommands::List(subargs) => {
-            if !subargs.paths && !subargs.names {
+            let json = subargs.format == Format::Json;
+            let mut entries = Vec::new();
+            if !subargs.paths && !subargs.names && !json {
                 println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
             }
             let mut exercises_done: u16 = 0;
@@ -165,19 +290,24 @@ fn main() {
                 let filter_cond = filters
                     .split(',')
                     .filter(|f| !f.trim().is_empty())
-                    .any(|f| e.name.contains(&f) || fname.contains(&f));
-                let status = if e.looks_done() {
+                    .any(|f| e.name.contains(f) || fname.contains(f));
+                let done = progress.is_done(e);
+                let status = if done {
                     exercises_done += 1;
                     "Done"
                 } else {
                     "Pending"
                 };
                 let solve_cond = {
-                    (e.looks_done() && subargs.solved)
-                        || (!e.looks_done() && subargs.unsolved)
+                    (done && subargs.solved)
+                        || (!done && subargs.unsolved)
                         || (!subargs.solved && !subargs.unsolved)
                 };
                 if solve_cond && (filter_cond || subargs.filter.is_none()) {
+                    if json {
+                        entries.push(ListEntry::new(e, done));
+                        return;
+                    }
                     let line = if subargs.paths {
                         format!("{fname}\n")
                     } else if subargs.names {
@@ -193,13 +323,17 @@ fn main() {
                         let mut handle = stdout.lock();
                         handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
                             match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
+                                std::io::ErrorKind::BrokenPipe => build_dir::exit(0),
+                                _ => build_dir::exit(1),
                             };
                         });
                     }
                 }
             });
+            if json {
+                print_json(&entries);
+                build_dir::exit(0);
+            }
             let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
             println!(
                 "Progress: You completed {} / {} exercises ({:.1} %).",
@@ -207,39 +341,98 @@ fn main() {
                 exercises.len(),
                 percentage_progress
             );
-            std::process::exit(0);
+            build_dir::exit(0);
         }
 
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &exercises, &progress);
 
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            let done = progress.is_done(exercise);
+            run(exercise, verbose, subargs.format, done).unwrap_or_else(|_| build_dir::exit(1));
         }
 
         Subcommands::Reset(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
-
-            reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            let to_reset: Vec<&Exercise> = match (&subargs.name, subargs.all) {
+                (Some(name), false) => vec![find_exercise(name, &exercises, &progress)],
+                (None, true) => exercises.iter().collect(),
+                _ => {
+                    println!("Give either the name of the exercise to reset, or --all");
+                    build_dir::exit(1);
+                }
+            };
+            let backup_dir = if subargs.backup {
+                Some(Path::new(BACKUP_DIR))
+            } else {
+                None
+            };
+
+            let mut reset_count = 0;
+            let mut failed = false;
+            for exercise in to_reset {
+                match reset(exercise, &pristine, backup_dir) {
+                    Ok(true) => {
+                        reset_count += 1;
+                        if let Err(e) = progress.forget(exercise) {
+                            println!("Failed to save the progress: {e}");
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(()) => failed = true,
+                }
+            }
+            if reset_count == 0 && !failed && subargs.all {
+                println!("Nothing to reset, all the exercises are as they were given.");
+            } else if reset_count == 0 && !failed {
+                println!("Nothing to reset, the exercise is as it was given.");
+            }
+            if failed {
+                build_dir::exit(1);
+            }
         }
 
         Subcommands::Hint(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
-
-            println!("{}", exercise.hint);
+            let exercise = find_exercise(&subargs.name, &exercises, &progress);
+
+            let mut revealed_hints = RevealedHints::load(HINTS_FILE);
+            let revealed = revealed_hints
+                .reveal_next(&exercise.name, &exercise.hint)
+                .unwrap_or_else(|_| {
+                    warn!("Could not save your revealed hints to {}", HINTS_FILE);
+                    revealed_hints.revealed(&exercise.name)
+                });
+            exercise.hint.print(revealed);
+            if revealed < exercise.hint.levels() {
+                println!();
+                println!(
+                    "Run `rustlings hint {}` again for the next hint.",
+                    exercise.name
+                );
+            }
         }
 
-        Subcommands::Verify(_subargs) => {
-            verify(&exercises, (0, exercises.len()), verbose)
-                .unwrap_or_else(|_| std::process::exit(1));
+        Subcommands::Verify(subargs) => {
+            let jobs = subargs.jobs.max(1);
+            verify(
+                &exercises,
+                (0, exercises.len()),
+                verbose,
+                jobs,
+                subargs.format,
+                &mut progress,
+            )
+            .unwrap_or_else(|_| build_dir::exit(1));
         }
 
+        // Packs are managed before looking for an info.toml
+        Subcommands::Pack(_) => unreachable!(),
+
         Subcommands::Lsp(_subargs) => {
             let mut project = RustAnalyzerProject::new();
             project
                 .get_sysroot_src()
                 .expect("Couldn't find toolchain path, do you have `rustc` installed?");
             project
-                .exercises_to_json()
+                .exercises_to_json(&exercises)
                 .expect("Couldn't parse rustlings exercises files");
 
             if project.crates.is_empty() {
@@ -252,14 +445,14 @@ fn main() {
             }
         }
 
-        Subcommands::Watch(_subargs) => match watch(&exercises, verbose) {
+        Subcommands::Watch(_subargs) => match watch(&exercises, verbose, &mut progress) {
             Err(e) => {
                 println!(
                     "Error: Could not watch your progress. Error message was {:?}.",
                     e
                 );
                 println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
-                std::process::exit(1);
+                build_dir::exit(1);
             }
             Ok(WatchStatus::Finished) => {
                 println!(
@@ -274,10 +467,55 @@ fn main() {
             }
         },
     }
+
+    build_dir::clean_run();
+}
+
+fn manage_packs(command: &PackSubcommands, packs: &mut Packs) -> Result<(), String> {
+    match command {
+        PackSubcommands::List(_) => {
+            let current = packs.current().map(|(name, _)| name);
+            for (name, dir) in packs.iter() {
+                let marker = if Some(name) == current { "*" } else { " " };
+                println!("{marker} {name:<17}\t{}", dir.display());
+            }
+        }
+        PackSubcommands::Use(subargs) => {
+            packs.select(&subargs.name)?;
+            println!("Using the '{}' pack.", subargs.name);
+        }
+        PackSubcommands::Add(subargs) => {
+            let name = packs.add(&subargs.path, subargs.name.clone())?;
+            let dir = packs
+                .iter()
+                .find(|(added, _)| *added == name)
+                .map(|(_, dir)| dir);
+            // The exercises of a pack are as they were given when it is added,
+            // so this is when they are kept for `reset`
+            if let Err(e) = dir.map_or(Ok(()), seal_pack) {
+                println!(
+                    "Could not keep the exercises as they were given, so they can't be reset: {e}"
+                );
+            }
+            println!("Added the '{name}' pack. Start using it with `rustlings pack use {name}`.");
+        }
+    }
+    Ok(())
+}
+
+// Keep the exercises of the pack in the given directory as they are now
+fn seal_pack(dir: &Path) -> io::Result<()> {
+    // The paths of the exercises are relative to the directory of their pack
+    std::env::set_current_dir(dir)?;
+    let toml_str = fs::read_to_string("info.toml")?;
+    let exercise_list = toml::from_str::<ExerciseList>(&toml_str)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Pristine::seal(PRISTINE_DIR, GIVEN_FILE, &exercise_list.exercises)?;
+    Ok(())
 }
 
 fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
+    failed_exercise_hint: &Arc<Mutex<Option<(String, Hints)>>>,
     should_quit: Arc<AtomicBool>,
 ) {
     let failed_exercise_hint = Arc::clone(failed_exercise_hint);
@@ -287,9 +525,23 @@ fn spawn_watch_shell(
         match io::stdin().read_line(&mut input) {
             Ok(_) => {
                 let input = input.trim();
-                if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
+                if input == "hint" || input == "hint more" {
+                    if let Some((name, hints)) = &*failed_exercise_hint.lock().unwrap() {
+                        // Read the file again, as `rustlings hint` may have revealed more
+                        let mut revealed_hints = RevealedHints::load(HINTS_FILE);
+                        let revealed = if input == "hint more" {
+                            revealed_hints.reveal_next(name, hints).unwrap_or_else(|_| {
+                                println!("Could not save your revealed hints to {HINTS_FILE}");
+                                revealed_hints.revealed(name)
+                            })
+                        } else {
+                            revealed_hints.revealed(name)
+                        };
+                        hints.print(revealed);
+                        if revealed < hints.levels() {
+                            println!();
+                            println!("Type 'hint more' for the next hint.");
+                        }
                     }
                 } else if input == "clear" {
                     println!("\x1B[2J\x1B[1;1H");
@@ -298,10 +550,11 @@ fn spawn_watch_shell(
                     println!("Bye!");
                 } else if input.eq("help") {
                     println!("Commands available to you in watch mode:");
-                    println!("  hint  - prints the current exercise's hint");
-                    println!("  clear - clears the screen");
-                    println!("  quit  - quits watch mode");
-                    println!("  help  - displays this help message");
+                    println!("  hint      - prints the current exercise's hint");
+                    println!("  hint more - reveals the next level of the hint");
+                    println!("  clear     - clears the screen");
+                    println!("  quit      - quits watch mode");
+                    println!("  help      - displays this help message");
                     println!();
                     println!("Watch mode automatically re-evaluates the current exercise");
                     println!("when you edit a file's contents.")
@@ -314,15 +567,15 @@ fn spawn_watch_shell(
     });
 }
 
-fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+fn find_exercise<'a>(name: &str, exercises: &'a [Exercise], progress: &Progress) -> &'a Exercise {
     if name.eq("next") {
         exercises
             .iter()
-            .find(|e| !e.looks_done())
+            .find(|e| !progress.is_done(e))
             .unwrap_or_else(|| {
                 println!("🎉 Congratulations! You have done all the exercises!");
                 println!("🔚 There are no more exercises to do next!");
-                std::process::exit(1)
+                build_dir::exit(1)
             })
     } else {
         exercises
@@ -330,7 +583,7 @@ fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
             .find(|e| e.name == name)
             .unwrap_or_else(|| {
                 println!("No exercise found for '{name}'!");
-                std::process::exit(1)
+                build_dir::exit(1)
             })
     }
 }
@@ -340,7 +593,11 @@ enum WatchStatus {
     Unfinished,
 }
 
-fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<WatchStatus> {
+fn watch(
+    exercises: &[Exercise],
+    verbose: bool,
+    progress: &mut Progress,
+) -> notify::Result<WatchStatus> {
     /* Clears the terminal with an ANSI escape code.
     Works in UNIX and newer Windows terminals. */
     fn clear_screen() {
@@ -351,12 +608,26 @@ fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<WatchStatus> {
     let should_quit = Arc::new(AtomicBool::new(false));
 
     let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
-    watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    // Packs do not need to keep their exercises in an exercises directory
+    let exercises_dir = Path::new("./exercises");
+    let watched = if exercises_dir.is_dir() {
+        exercises_dir
+    } else {
+        Path::new(".")
+    };
+    watcher.watch(watched, RecursiveMode::Recursive)?;
 
     clear_screen();
 
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(exercises.iter(), (0, exercises.len()), verbose) {
+    let to_owned_hint = |t: &Exercise| (t.name.clone(), t.hint.clone());
+    let failed_exercise_hint = match verify(
+        exercises.iter(),
+        (0, exercises.len()),
+        verbose,
+        1,
+        Format::Text,
+        progress,
+    ) {
         Ok(_) => return Ok(WatchStatus::Finished),
         Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
     };
@@ -364,26 +635,36 @@ fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<WatchStatus> {
     loop {
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
-                        let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(pending_exercises, (num_done, exercises.len()), verbose) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
+                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b)
+                    if (b.extension() == Some(OsStr::new("rs"))
+                        || b.extension() == Some(OsStr::new("toml")))
+                        && b.exists() =>
+                {
+                    let filepath = b.as_path().canonicalize().unwrap();
+                    let pending_exercises: Vec<&Exercise> = exercises
+                        .iter()
+                        .find(|e| e.contains(&filepath))
+                        .into_iter()
+                        .chain(
+                            exercises
+                                .iter()
+                                .filter(|e| !progress.is_done(e) && !e.contains(&filepath)),
+                        )
+                        .collect();
+                    let num_done = exercises.iter().filter(|e| progress.is_done(e)).count();
+                    clear_screen();
+                    match verify(
+                        pending_exercises,
+                        (num_done, exercises.len()),
+                        verbose,
+                        1,
+                        Format::Text,
+                        progress,
+                    ) {
+                        Ok(_) => return Ok(WatchStatus::Finished),
+                        Err(exercise) => {
+                            let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
+                            *failed_exercise_hint = Some(to_owned_hint(exercise));
                         }
                     }
                 }
@@ -403,7 +684,7 @@ fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<WatchStatus> {
 
 fn rustc_exists() -> bool {
     Command::new("rustc")
-        .args(&["--version"])
+        .args(["--version"])
         .stdout(Stdio::null())
         .spawn()
         .and_then(|mut child| child.wait())
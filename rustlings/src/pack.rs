@@ -0,0 +1,177 @@
+use crate::exercise::ExerciseList;
+use crate::state_file::StateFile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// The file, in the rustlings home directory, where the installed packs are recorded
+pub const PACKS_FILE: &str = "packs.json";
+
+// The directory where rustlings keeps what is shared by all the packs:
+// $RUSTLINGS_HOME if it is set, and ~/.rustlings otherwise
+pub fn rustlings_home() -> Option<PathBuf> {
+    match env::var_os("RUSTLINGS_HOME") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => home::home_dir().map(|home| home.join(".rustlings")),
+    }
+}
+
+// The exercise packs that were installed with `rustlings pack add`.
+// A pack is a directory with its own info.toml, exercises and progress,
+// which can live anywhere, not only in the rustlings checkout
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Packs {
+    // The pack used when rustlings is run outside a directory with an info.toml
+    current: Option<String>,
+    // Name of each pack and the directory of its info.toml
+    packs: BTreeMap<String, PathBuf>,
+    // Where the packs are saved
+    #[serde(skip)]
+    file: StateFile,
+}
+
+impl Packs {
+    // Load the packs saved in the given file.
+    // A missing file means that no pack has been added yet.
+    // A file that can't be read or parsed is left alone, so that no pack is lost
+    // by adding or selecting one before the file is fixed
+    pub fn load(path: impl AsRef<Path>) -> Packs {
+        let (file, mut packs) = StateFile::load::<Packs>(path);
+        packs.file = file;
+        packs
+    }
+
+    // Whether the file exists but could not be read, so the packs are not saved
+    pub fn is_unreadable(&self) -> bool {
+        self.file.is_unreadable()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.file.path().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        self.file.save(self)
+    }
+
+    // Add the pack in the given directory, under the given name
+    // or, without one, under the name of the directory
+    pub fn add(&mut self, dir: &Path, name: Option<String>) -> Result<String, String> {
+        let dir = dir
+            .canonicalize()
+            .map_err(|_| format!("{} is not a directory", dir.display()))?;
+        let info = fs::read_to_string(dir.join("info.toml"))
+            .map_err(|_| format!("{} has no info.toml", dir.display()))?;
+        toml::from_str::<ExerciseList>(&info)
+            .map_err(|e| format!("The info.toml of {} is not valid: {e}", dir.display()))?;
+
+        let name = match name {
+            Some(name) => name,
+            None => dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .ok_or_else(|| format!("Give {} a name with --name", dir.display()))?,
+        };
+        if self.packs.contains_key(&name) {
+            return Err(format!("There already is a pack named '{name}'"));
+        }
+        self.packs.insert(name.clone(), dir);
+        self.save().map_err(|e| {
+            format!(
+                "Could not save the packs to {}: {e}",
+                self.file.path().display()
+            )
+        })?;
+        Ok(name)
+    }
+
+    // Make the pack with the given name the current one
+    pub fn select(&mut self, name: &str) -> Result<(), String> {
+        if !self.packs.contains_key(name) {
+            return Err(format!("No pack found for '{name}'!"));
+        }
+        self.current = Some(name.to_string());
+        self.save().map_err(|e| {
+            format!(
+                "Could not save the packs to {}: {e}",
+                self.file.path().display()
+            )
+        })
+    }
+
+    // The name and directory of the current pack, if one was selected
+    pub fn current(&self) -> Option<(&str, &Path)> {
+        let name = self.current.as_deref()?;
+        self.packs.get(name).map(|dir| (name, dir.as_path()))
+    }
+
+    // The name and directory of every pack, in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.packs
+            .iter()
+            .map(|(name, dir)| (name.as_str(), dir.as_path()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_packs_file(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rustlings_packs_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(PACKS_FILE)
+    }
+
+    #[test]
+    fn test_add_and_select() {
+        let path = temp_packs_file("add");
+        let pack = Path::new("tests/fixture/state");
+
+        let mut packs = Packs::load(&path);
+        assert!(packs.current().is_none());
+        assert_eq!(packs.add(pack, None), Ok("state".to_string()));
+        assert!(packs.add(pack, None).is_err());
+        assert_eq!(
+            packs.add(pack, Some("course".into())),
+            Ok("course".to_string())
+        );
+        packs.select("course").unwrap();
+
+        let reloaded = Packs::load(&path);
+        let (name, dir) = reloaded.current().unwrap();
+        assert_eq!(name, "course");
+        assert_eq!(dir, pack.canonicalize().unwrap());
+        assert_eq!(
+            reloaded.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["course", "state"]
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_add_needs_an_info_toml() {
+        let path = temp_packs_file("invalid");
+        let mut packs = Packs::load(&path);
+
+        assert!(packs.add(Path::new("tests/fixture"), None).is_err());
+        assert!(packs.add(Path::new("tests/fixture/missing"), None).is_err());
+        assert!(packs.select("missing").is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_unreadable_file_is_not_overwritten() {
+        let path = temp_packs_file("broken");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not json").unwrap();
+
+        let mut packs = Packs::load(&path);
+        assert!(packs.is_unreadable());
+        assert!(packs.add(Path::new("tests/fixture/state"), None).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
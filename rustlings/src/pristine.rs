@@ -0,0 +1,345 @@
+use crate::exercise::{collect_files, Exercise, Limits};
+use crate::hint::Hints;
+use crate::progress::source_hash;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+// The directory, next to info.toml, where the exercises are kept as they were given
+pub const PRISTINE_DIR: &str = ".rustlings-pristine";
+
+// The file, next to info.toml, with the hash of each exercise as it is given.
+// A pack ships it, or it is written when the pack is added
+pub const GIVEN_FILE: &str = ".rustlings-given.json";
+
+// The directory, next to info.toml, where `reset --backup` keeps the last attempt at each exercise
+pub const BACKUP_DIR: &str = ".rustlings-backup";
+
+// What resetting an exercise did
+#[derive(PartialEq, Debug)]
+pub enum Reset {
+    // The exercise was brought back to how it was given,
+    // and the attempt was backed up to the given path if asked to
+    Restored(Option<PathBuf>),
+    // The exercise was still as it was given
+    Unchanged,
+}
+
+// Copies of the exercises as they were given, checked against the hashes of the pack.
+// A copy is only taken of an exercise that matches its hash, so that it is never
+// a copy of an attempt, and it is taken again when the pack gives a new version.
+// Each copy mirrors the path of its exercise under the directory of the copies.
+pub struct Pristine {
+    dir: PathBuf,
+    // The hash of each exercise as it is given, by name
+    given: BTreeMap<String, String>,
+}
+
+impl Pristine {
+    // The copies in the given directory, checked against the hashes in the given file.
+    // Without the file, no exercise can be told to be as it was given
+    pub fn load(dir: impl Into<PathBuf>, given_file: impl AsRef<Path>) -> Pristine {
+        let given = fs::read_to_string(given_file)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Pristine {
+            dir: dir.into(),
+            given,
+        }
+    }
+
+    // Write the hashes of the exercises as they are now to the given file, unless the pack
+    // ships it already, and keep a copy of them.
+    // Only meant for when the exercises are known to be as they were given,
+    // like when their pack is added
+    pub fn seal(
+        dir: impl Into<PathBuf>,
+        given_file: impl AsRef<Path>,
+        exercises: &[Exercise],
+    ) -> io::Result<Pristine> {
+        let given_file = given_file.as_ref();
+        if !given_file.exists() {
+            let mut given = BTreeMap::new();
+            for exercise in exercises.iter().filter(|exercise| exercise.path.exists()) {
+                given.insert(exercise.name.clone(), source_hash(exercise)?);
+            }
+            let contents = serde_json::to_string_pretty(&given)?;
+            fs::write(given_file, contents + "\n")?;
+        }
+        let pristine = Pristine::load(dir, given_file);
+        pristine.keep(exercises)?;
+        Ok(pristine)
+    }
+
+    // Keep a copy of every exercise that is as it was given, unless its copy already is.
+    // A copy of an older version of the exercise is replaced once the exercise is as given
+    pub fn keep(&self, exercises: &[Exercise]) -> io::Result<()> {
+        for exercise in exercises {
+            let given = match self.given.get(&exercise.name) {
+                Some(given) => given,
+                None => continue,
+            };
+            if !self.matches(exercise, given) || self.copy_matches(exercise, given) {
+                continue;
+            }
+            let copy = mirror(&self.dir, &exercise.path);
+            remove_tree(&copy)?;
+            if let Err(e) = copy_tree(&exercise.path, &copy) {
+                // A partial copy would be mistaken for the exercise as it was given
+                let _ignored = remove_tree(&copy);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // Bring the exercise back to its copy, first copying the attempt
+    // under the given backup directory if there is one.
+    // The files that were added to a Cargo project exercise are removed.
+    // Fails without touching the exercise when there is no copy that matches its hash
+    pub fn restore(&self, exercise: &Exercise, backup_dir: Option<&Path>) -> io::Result<Reset> {
+        let given = match self.given.get(&exercise.name) {
+            Some(given) => given,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("The pack does not tell how {exercise} was given, in {GIVEN_FILE}"),
+                ))
+            }
+        };
+        if self.matches(exercise, given) {
+            return Ok(Reset::Unchanged);
+        }
+        if !self.copy_matches(exercise, given) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "No copy of {exercise} as it was given was kept, because rustlings never saw it unchanged"
+                ),
+            ));
+        }
+        let copy = mirror(&self.dir, &exercise.path);
+
+        let backup = match backup_dir {
+            Some(dir) if exercise.path.exists() => {
+                let backup = mirror(dir, &exercise.path);
+                remove_tree(&backup)?;
+                copy_tree(&exercise.path, &backup)?;
+                Some(backup)
+            }
+            _ => None,
+        };
+        if exercise.path.exists() {
+            let given = relative_files(&copy)?;
+            for file in relative_files(&exercise.path)? {
+                if !given.contains(&file) {
+                    fs::remove_file(join(&exercise.path, &file))?;
+                }
+            }
+        }
+        copy_tree(&copy, &exercise.path)?;
+        Ok(Reset::Restored(backup))
+    }
+
+    // Whether the exercise is as it was given
+    fn matches(&self, exercise: &Exercise, given: &str) -> bool {
+        exercise.path.exists() && source_hash(exercise).ok().as_deref() == Some(given)
+    }
+
+    // Whether the copy of the exercise is as it was given
+    fn copy_matches(&self, exercise: &Exercise, given: &str) -> bool {
+        let copy = Exercise {
+            name: exercise.name.clone(),
+            path: mirror(&self.dir, &exercise.path),
+            mode: exercise.mode,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        };
+        self.matches(&copy, given)
+    }
+}
+
+// The path under `dir` that mirrors the given path
+fn mirror(dir: &Path, path: &Path) -> PathBuf {
+    let relative: PathBuf = path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    dir.join(relative)
+}
+
+// The path of the given file inside `root`, where the empty path is `root` itself
+// for the exercises that are a single file
+fn join(root: &Path, file: &Path) -> PathBuf {
+    if file.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(file)
+    }
+}
+
+// The files of a single file or of a Cargo project, relative to it
+fn relative_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        return Ok(vec![PathBuf::new()]);
+    }
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    files.sort();
+    Ok(files
+        .into_iter()
+        .filter_map(|file| file.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect())
+}
+
+fn copy_tree(from: &Path, to: &Path) -> io::Result<()> {
+    for file in relative_files(from)? {
+        let target = join(to, &file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(join(from, &file), target)?;
+    }
+    Ok(())
+}
+
+fn remove_tree(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use std::env;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rustlings_pristine_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn exercise(path: PathBuf, mode: Mode) -> Exercise {
+        Exercise {
+            name: "example".into(),
+            path,
+            mode,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        }
+    }
+
+    #[test]
+    fn test_restore_single_file() {
+        let dir = temp_dir("file");
+        let exercise = exercise(dir.join("exercises/example.rs"), Mode::Compile);
+        fs::create_dir_all(exercise.path.parent().unwrap()).unwrap();
+        fs::write(&exercise.path, "// given").unwrap();
+        let exercises = std::slice::from_ref(&exercise);
+        let pristine =
+            Pristine::seal(dir.join(PRISTINE_DIR), dir.join(GIVEN_FILE), exercises).unwrap();
+        assert_eq!(pristine.restore(&exercise, None).unwrap(), Reset::Unchanged);
+
+        // Later edits do not change the copy
+        fs::write(&exercise.path, "// attempt").unwrap();
+        pristine.keep(exercises).unwrap();
+        let backup_dir = dir.join(BACKUP_DIR);
+        let reset = pristine.restore(&exercise, Some(&backup_dir)).unwrap();
+        let backup = mirror(&backup_dir, &exercise.path);
+        assert_eq!(reset, Reset::Restored(Some(backup.clone())));
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), "// given");
+        assert_eq!(fs::read_to_string(backup).unwrap(), "// attempt");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_cargo_project() {
+        let dir = temp_dir("cargo");
+        let exercise = exercise(dir.join("project"), Mode::Cargo);
+        fs::create_dir_all(exercise.path.join("src")).unwrap();
+        fs::write(exercise.path.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(exercise.path.join("src/main.rs"), "// given").unwrap();
+        let exercises = std::slice::from_ref(&exercise);
+        let pristine =
+            Pristine::seal(dir.join(PRISTINE_DIR), dir.join(GIVEN_FILE), exercises).unwrap();
+
+        fs::write(exercise.path.join("src/main.rs"), "// attempt").unwrap();
+        fs::write(exercise.path.join("src/extra.rs"), "// added").unwrap();
+        fs::remove_file(exercise.path.join("Cargo.toml")).unwrap();
+        fs::create_dir_all(exercise.path.join("target")).unwrap();
+        fs::write(exercise.path.join("target/build"), "").unwrap();
+
+        let reset = pristine.restore(&exercise, None).unwrap();
+        assert_eq!(reset, Reset::Restored(None));
+        assert_eq!(
+            fs::read_to_string(exercise.path.join("src/main.rs")).unwrap(),
+            "// given"
+        );
+        assert!(exercise.path.join("Cargo.toml").exists());
+        assert!(!exercise.path.join("src/extra.rs").exists());
+        assert!(exercise.path.join("target/build").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_copies_only_what_was_given() {
+        let dir = temp_dir("given");
+        let exercise = exercise(dir.join("example.rs"), Mode::Compile);
+        let exercises = std::slice::from_ref(&exercise);
+        fs::write(&exercise.path, "// given").unwrap();
+        let given = source_hash(&exercise).unwrap();
+        fs::write(dir.join(GIVEN_FILE), format!(r#"{{"example": "{given}"}}"#)).unwrap();
+
+        // An attempt is never mistaken for the exercise as it was given
+        fs::write(&exercise.path, "// attempt").unwrap();
+        let pristine = Pristine::load(dir.join(PRISTINE_DIR), dir.join(GIVEN_FILE));
+        pristine.keep(exercises).unwrap();
+        assert!(pristine.restore(&exercise, None).is_err());
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), "// attempt");
+
+        // Without the hashes, nothing can be reset
+        let unknown = Pristine::load(dir.join(PRISTINE_DIR), dir.join("missing.json"));
+        assert!(unknown.restore(&exercise, None).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_new_version_replaces_the_copy() {
+        let dir = temp_dir("update");
+        let exercise = exercise(dir.join("example.rs"), Mode::Compile);
+        let exercises = std::slice::from_ref(&exercise);
+        fs::write(&exercise.path, "// first version").unwrap();
+        Pristine::seal(dir.join(PRISTINE_DIR), dir.join(GIVEN_FILE), exercises).unwrap();
+
+        // The pack gives a new version, which the copy is not anymore
+        fs::write(&exercise.path, "// second version").unwrap();
+        let given = source_hash(&exercise).unwrap();
+        fs::write(dir.join(GIVEN_FILE), format!(r#"{{"example": "{given}"}}"#)).unwrap();
+        let pristine = Pristine::load(dir.join(PRISTINE_DIR), dir.join(GIVEN_FILE));
+        fs::write(&exercise.path, "// attempt").unwrap();
+        assert!(pristine.restore(&exercise, None).is_err());
+
+        // Once the exercise is as given again, it is copied anew
+        fs::write(&exercise.path, "// second version").unwrap();
+        pristine.keep(exercises).unwrap();
+        fs::write(&exercise.path, "// attempt").unwrap();
+        assert_eq!(
+            pristine.restore(&exercise, None).unwrap(),
+            Reset::Restored(None)
+        );
+        assert_eq!(
+            fs::read_to_string(&exercise.path).unwrap(),
+            "// second version"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+use crate::exercise::{Exercise, Mode};
+use crate::state_file::StateFile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The file, next to info.toml, where the exercises that passed `verify` are recorded
+pub const PROGRESS_FILE: &str = ".rustlings-progress.json";
+
+// The exercises that passed `verify`, each with the hash of the source that passed.
+// An exercise only counts as done while its source still matches that hash, so
+// editing a solved exercise makes it pending again until it is verified once more.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Progress {
+    // Name of each verified exercise and the hash of its passing source
+    exercises: BTreeMap<String, String>,
+    // Where the progress is saved
+    #[serde(skip)]
+    file: StateFile,
+    // Whether exercises also need their `I AM NOT DONE` marker removed to be done
+    #[serde(skip)]
+    require_marker: bool,
+}
+
+impl Progress {
+    // Load the progress saved in the given file.
+    // A missing file means that no exercise has been verified yet.
+    // A file that can't be read or parsed is left alone: no exercise counts as verified,
+    // and nothing is saved over it, so that fixing the file brings the progress back
+    pub fn load(path: impl AsRef<Path>, require_marker: bool) -> Progress {
+        let (file, mut progress) = StateFile::load::<Progress>(path);
+        progress.file = file;
+        progress.require_marker = require_marker;
+        progress
+    }
+
+    // Whether the file exists but could not be read, so the progress is not saved
+    pub fn is_unreadable(&self) -> bool {
+        self.file.is_unreadable()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        self.file.save(self)
+    }
+
+    // Whether the marker is needed on top of a passing `verify`
+    pub fn require_marker(&self) -> bool {
+        self.require_marker
+    }
+
+    // Check that the exercise passed `verify` with its current source and,
+    // when the marker is required, that the marker was removed
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        let verified = match (self.exercises.get(&exercise.name), source_hash(exercise)) {
+            (Some(recorded), Ok(current)) => *recorded == current,
+            _ => false,
+        };
+        verified && (!self.require_marker || exercise.looks_done())
+    }
+
+    // Record that the exercise passed `verify` with its current source
+    pub fn record(&mut self, exercise: &Exercise) -> io::Result<()> {
+        let hash = source_hash(exercise)?;
+        self.exercises.insert(exercise.name.clone(), hash);
+        self.save()
+    }
+
+    // Forget that the exercise was verified, if it ever was
+    pub fn forget(&mut self, exercise: &Exercise) -> io::Result<()> {
+        if self.exercises.remove(&exercise.name).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+}
+
+// Hash the source of the exercise with 64-bit FNV-1a.
+// Unlike the standard library's hasher, it is stable across Rust versions,
+// so the progress file stays valid when the toolchain is updated.
+// The files of a Cargo project exercise are hashed together with their paths,
+// so that renaming or moving a file also counts as a change.
+// Line endings are hashed as `\n`, so that a checkout with `\r\n` line endings hashes the same.
+pub fn source_hash(exercise: &Exercise) -> io::Result<String> {
+    let files = exercise.files()?;
+    let mut hash = 0xcbf29ce484222325_u64;
+    let mut write = |bytes: &[u8]| {
+        for byte in bytes {
+            hash = (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    };
+    for file in &files {
+        if let Mode::Cargo = exercise.mode {
+            // Join the components with `/` so that the hash is the same on every platform
+            let path = file.strip_prefix(&exercise.path).unwrap_or(file);
+            let components: Vec<_> = path.iter().map(|c| c.to_string_lossy()).collect();
+            write(components.join("/").as_bytes());
+        }
+        let contents = fs::read(file)?;
+        for line in contents.split_inclusive(|byte| *byte == b'\n') {
+            match line.strip_suffix(b"\r\n") {
+                Some(line) => {
+                    write(line);
+                    write(b"\n");
+                }
+                None => write(line),
+            }
+        }
+    }
+    Ok(format!("{hash:016x}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Limits;
+    use crate::hint::Hints;
+    use std::env;
+    use std::path::PathBuf;
+
+    const FINISHED: &str = "tests/fixture/state/finished_exercise.rs";
+    const PENDING: &str = "tests/fixture/state/pending_exercise.rs";
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise {
+            name: name.into(),
+            path: PathBuf::from(path),
+            mode: Mode::Compile,
+            hint: Hints::default(),
+            limits: Limits::default(),
+        }
+    }
+
+    fn temp_progress_file(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("rustlings_{}_{name}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_source_hash() {
+        let finished = exercise("finished_exercise", FINISHED);
+        let pending = exercise("pending_exercise", PENDING);
+
+        assert_eq!(
+            source_hash(&finished).unwrap(),
+            source_hash(&finished).unwrap()
+        );
+        assert_ne!(
+            source_hash(&finished).unwrap(),
+            source_hash(&pending).unwrap()
+        );
+        assert_eq!(source_hash(&finished).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_source_hash_ignores_line_endings() {
+        let source = env::temp_dir().join(format!("rustlings_{}_crlf.rs", std::process::id()));
+        let crlf = exercise("crlf", source.to_str().unwrap());
+        fs::write(&source, "fn main() {}\r\n").unwrap();
+        let with_crlf = source_hash(&crlf).unwrap();
+        fs::write(&source, "fn main() {}\n").unwrap();
+        assert_eq!(source_hash(&crlf).unwrap(), with_crlf);
+        fs::remove_file(&source).unwrap();
+    }
+
+    #[test]
+    fn test_record_and_reload() {
+        let path = temp_progress_file("record");
+        let finished = exercise("finished_exercise", FINISHED);
+
+        let mut progress = Progress::load(&path, true);
+        assert!(!progress.is_done(&finished));
+        progress.record(&finished).unwrap();
+        assert!(progress.is_done(&finished));
+
+        let reloaded = Progress::load(&path, true);
+        assert!(reloaded.is_done(&finished));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_changed_source_is_not_done() {
+        let path = temp_progress_file("changed");
+        let source = env::temp_dir().join(format!("rustlings_{}_changed.rs", std::process::id()));
+        fs::write(&source, "fn main() {}\n").unwrap();
+        let changed = exercise("changed", source.to_str().unwrap());
+
+        let mut progress = Progress::load(&path, false);
+        progress.record(&changed).unwrap();
+        assert!(progress.is_done(&changed));
+        fs::write(&source, "fn main() { loop {} }\n").unwrap();
+        assert!(!progress.is_done(&changed));
+
+        fs::remove_file(&source).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_marker_is_an_optional_gate() {
+        let path = temp_progress_file("marker");
+        let pending = exercise("pending_exercise", PENDING);
+
+        let mut progress = Progress::load(&path, true);
+        progress.record(&pending).unwrap();
+        assert!(!progress.is_done(&pending));
+        assert!(Progress::load(&path, false).is_done(&pending));
+
+        progress.forget(&pending).unwrap();
+        assert!(!Progress::load(&path, false).is_done(&pending));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_means_no_progress() {
+        let path = temp_progress_file("missing");
+        let finished = exercise("finished_exercise", FINISHED);
+
+        let mut progress = Progress::load(&path, false);
+        assert!(!progress.is_unreadable());
+        assert!(!progress.is_done(&finished));
+        progress.record(&finished).unwrap();
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_file_is_not_overwritten() {
+        let path = temp_progress_file("broken");
+        fs::write(&path, "not json").unwrap();
+        let finished = exercise("finished_exercise", FINISHED);
+
+        let mut progress = Progress::load(&path, false);
+        assert!(progress.is_unreadable());
+        assert!(!progress.is_done(&finished));
+        assert!(progress.record(&finished).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+        fs::remove_file(&path).unwrap();
+    }
+}
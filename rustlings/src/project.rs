@@ -1,7 +1,10 @@
+use crate::exercise::{Exercise, Mode};
 use glob::glob;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Contains the structure of resulting rust-project.json file
@@ -16,10 +19,32 @@ pub struct RustAnalyzerProject {
 pub struct Crate {
     root_module: String,
     edition: String,
-    deps: Vec<String>,
+    deps: Vec<Dependency>,
     cfg: Vec<String>,
 }
 
+/// A dependency of a crate on another crate of `rust-project.json`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Dependency {
+    /// Index of the crate depended on
+    #[serde(rename = "crate")]
+    krate: usize,
+    /// Name under which the crate is used
+    name: String,
+}
+
+impl Crate {
+    fn new(root_module: String, deps: Vec<Dependency>) -> Crate {
+        Crate {
+            root_module,
+            edition: "2021".to_string(),
+            deps,
+            // This allows rust_analyzer to work inside #[test] blocks
+            cfg: vec!["test".to_string()],
+        }
+    }
+}
+
 impl RustAnalyzerProject {
     pub fn new() -> RustAnalyzerProject {
         RustAnalyzerProject {
@@ -41,24 +66,59 @@ impl RustAnalyzerProject {
     fn path_to_json(&mut self, path: String) {
         if let Some((_, ext)) = path.split_once('.') {
             if ext == "rs" {
-                self.crates.push(Crate {
-                    root_module: path,
-                    edition: "2021".to_string(),
-                    deps: Vec::new(),
-                    // This allows rust_analyzer to work inside #[test] blocks
-                    cfg: vec!["test".to_string()],
-                })
+                self.crates.push(Crate::new(path, Vec::new()))
             }
         }
     }
 
-    /// Parse the exercises folder for .rs files, any matches will create
-    /// a new `crate` in rust-project.json which allows rust-analyzer to
-    /// treat it like a normal binary
-    pub fn exercises_to_json(&mut self) -> Result<(), Box<dyn Error>> {
-        for e in glob("./exercises/**/*")? {
-            let path = e?.to_string_lossy().to_string();
-            self.path_to_json(path);
+    /// Add a `crate` to rust-project.json for each exercise of info.toml,
+    /// which allows rust-analyzer to treat it like a normal binary.
+    /// The exercises are taken from info.toml rather than from the exercises folder,
+    /// as a pack can keep them anywhere next to it.
+    /// The files of Cargo project exercises are modules of their crates instead,
+    /// so only the roots of those crates are added
+    pub fn exercises_to_json(&mut self, exercises: &[Exercise]) -> Result<(), Box<dyn Error>> {
+        for exercise in exercises {
+            match exercise.mode {
+                Mode::Cargo => self.cargo_project_to_json(&exercise.path)?,
+                _ => self.path_to_json(exercise.path.to_string_lossy().to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the crates of a Cargo project exercise: its library, if it has one,
+    /// and its binaries, integration tests and examples, which can use the library
+    fn cargo_project_to_json(&mut self, dir: &Path) -> Result<(), Box<dyn Error>> {
+        let manifest: toml::Value = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml"))?)?;
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .ok_or("Cargo.toml has no package name")?
+            .replace('-', "_");
+
+        let lib = dir.join("src").join("lib.rs");
+        let deps = if lib.exists() {
+            self.crates
+                .push(Crate::new(lib.to_string_lossy().to_string(), Vec::new()));
+            vec![Dependency {
+                krate: self.crates.len() - 1,
+                name,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        let mut roots: Vec<PathBuf> = vec![dir.join("src").join("main.rs")];
+        for pattern in ["src/bin/*.rs", "tests/*.rs", "examples/*.rs"] {
+            for root in glob(&dir.join(pattern).to_string_lossy())? {
+                roots.push(root?);
+            }
+        }
+        for root in roots.into_iter().filter(|root| root.exists()) {
+            let root = root.to_string_lossy().to_string();
+            self.crates.push(Crate::new(root, deps.clone()));
         }
         Ok(())
     }
@@ -84,7 +144,7 @@ impl RustAnalyzerProject {
 
         println!("Determined toolchain: {}\n", &toolchain);
 
-        self.sysroot_src = (std::path::Path::new(&*toolchain)
+        self.sysroot_src = (std::path::Path::new(toolchain)
             .join("lib")
             .join("rustlib")
             .join("src")
@@ -0,0 +1,221 @@
+use crate::build_dir;
+use crate::exercise::{Exercise, ExerciseOutput, Mode, RunError};
+use crate::verify::Outcome;
+use regex::Regex;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+// The escape sequences that the compiler and cargo use for colors
+const ANSI_ESCAPE_REGEX: &str = r"\x1b\[[0-9;]*[A-Za-z]";
+
+// How the results of `list`, `verify` and `run` are printed
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    // Tables, progress bars and colors for people
+    Text,
+    // JSON for scripts
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{s}', expected 'text' or 'json'")),
+        }
+    }
+}
+
+// Whether the exercise counts as done, as shown by `list`
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ExerciseState {
+    Done,
+    Pending,
+}
+
+impl From<bool> for ExerciseState {
+    fn from(done: bool) -> Self {
+        if done {
+            ExerciseState::Done
+        } else {
+            ExerciseState::Pending
+        }
+    }
+}
+
+// An exercise as printed by `list --format json`
+#[derive(Serialize, Debug)]
+pub struct ListEntry<'a> {
+    pub name: &'a str,
+    pub path: String,
+    pub mode: Mode,
+    pub state: ExerciseState,
+}
+
+impl<'a> ListEntry<'a> {
+    pub fn new(exercise: &'a Exercise, done: bool) -> Self {
+        ListEntry {
+            name: &exercise.name,
+            path: exercise.path.display().to_string(),
+            mode: exercise.mode,
+            state: done.into(),
+        }
+    }
+}
+
+// The number of tests in each state, summed over the test binaries of the exercise
+#[derive(Serialize, Default, PartialEq, Debug)]
+pub struct TestResults {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+impl TestResults {
+    // Read the `test result:` lines that the test harness prints at the end of a run
+    pub fn parse(stdout: &str) -> Option<TestResults> {
+        let mut results: Option<TestResults> = None;
+        for line in stdout.lines() {
+            let counts = match line.split_once("test result: ") {
+                Some((_, counts)) => counts,
+                None => continue,
+            };
+            let totals = results.get_or_insert_with(TestResults::default);
+            for count in counts.split(['.', ';']) {
+                let mut words = count.split_whitespace();
+                let (number, kind) = match (words.next(), words.next()) {
+                    (Some(number), Some(kind)) => (number, kind),
+                    _ => continue,
+                };
+                let number = match number.parse::<usize>() {
+                    Ok(number) => number,
+                    Err(_) => continue,
+                };
+                match kind {
+                    "passed" => totals.passed += number,
+                    "failed" => totals.failed += number,
+                    "ignored" => totals.ignored += number,
+                    _ => {}
+                }
+            }
+        }
+        results
+    }
+}
+
+// The result of checking an exercise, as printed by `verify` and `run` with `--format json`
+#[derive(Serialize, Debug)]
+pub struct ExerciseReport<'a> {
+    pub name: &'a str,
+    pub path: String,
+    pub mode: Mode,
+    pub state: ExerciseState,
+    // Whether the exercise compiled, and satisfied Clippy for Clippy exercises
+    pub compiled: bool,
+    // Whether the exercise compiled and its binary or tests ran successfully
+    pub passed: bool,
+    // Whether the binary or tests were stopped for running too long
+    pub timed_out: bool,
+    // The results of the tests, for the exercises that have tests and got to run them
+    pub tests: Option<TestResults>,
+    // The output of the compiler if the exercise did not compile,
+    // and otherwise the output of its binary or tests
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl<'a> ExerciseReport<'a> {
+    pub fn new(exercise: &'a Exercise, outcome: Outcome, done: bool) -> Self {
+        let (compiled, passed, timed_out, output) = match outcome {
+            Outcome::Passed(output) => (true, true, false, output),
+            Outcome::CompileFailed(error) => (false, false, false, Some(error.output)),
+            Outcome::RunFailed(RunError::Failed(output)) => (true, false, false, Some(output)),
+            Outcome::RunFailed(RunError::TimedOut(_, output)) => (true, false, true, Some(output)),
+        };
+        let ExerciseOutput { stdout, stderr } = output.unwrap_or(ExerciseOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+        });
+        let (stdout, stderr) = (strip_colors(&stdout), strip_colors(&stderr));
+        let tests = match exercise.mode {
+            Mode::Test | Mode::Cargo if compiled => TestResults::parse(&stdout),
+            _ => None,
+        };
+
+        ExerciseReport {
+            name: &exercise.name,
+            path: exercise.path.display().to_string(),
+            mode: exercise.mode,
+            state: done.into(),
+            compiled,
+            passed,
+            timed_out,
+            tests,
+            stdout,
+            stderr,
+        }
+    }
+}
+
+// Remove the colors from the output of the compiler, which scripts have no use for
+fn strip_colors(output: &str) -> String {
+    let re = Regex::new(ANSI_ESCAPE_REGEX).unwrap();
+    re.replace_all(output, "").to_string()
+}
+
+// Print the given value as JSON on stdout.
+// Like `list`, exit quietly when the output is piped into a command
+// that stops reading early
+pub fn print_json(value: &impl Serialize) {
+    let json = serde_json::to_string_pretty(value).expect("Failed to serialize to JSON");
+    writeln!(io::stdout().lock(), "{json}").unwrap_or_else(|e| match e.kind() {
+        io::ErrorKind::BrokenPipe => build_dir::exit(0),
+        _ => build_dir::exit(1),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_parse_test_results() {
+        let stdout = "\
+running 2 tests
+test tests::one ... ok
+test tests::two ... FAILED
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 1 test
+test result: ok. 1 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert_eq!(
+            TestResults::parse(stdout),
+            Some(TestResults {
+                passed: 2,
+                failed: 1,
+                ignored: 2,
+            })
+        );
+        assert_eq!(TestResults::parse("Hello, world!"), None);
+    }
+
+    #[test]
+    fn test_strip_colors() {
+        let stderr = "\u{1b}[1m\u{1b}[91merror\u{1b}[0m: expected pattern";
+        assert_eq!(strip_colors(stderr), "error: expected pattern");
+    }
+}
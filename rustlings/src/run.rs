@@ -1,32 +1,54 @@
-use std::process::Command;
+use std::path::Path;
 
-use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
+use crate::exercise::{Exercise, Mode, RunError};
+use crate::pristine::{Pristine, Reset};
+use crate::report::{print_json, ExerciseReport, Format};
+use crate::verify::{check, test, warn_timed_out, Outcome};
 use indicatif::ProgressBar;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
 // The verbose argument helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+// In the JSON format, the outcome is printed as a report instead,
+// which tells whether the exercise is done as given by `done`
+pub fn run(exercise: &Exercise, verbose: bool, format: Format, done: bool) -> Result<(), ()> {
+    if format == Format::Json {
+        let outcome = check(exercise, &ProgressBar::hidden());
+        let passed = matches!(outcome, Outcome::Passed(_));
+        print_json(&ExerciseReport::new(exercise, outcome, done));
+        return if passed { Ok(()) } else { Err(()) };
+    }
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
+        Mode::Test | Mode::Cargo => test(exercise, verbose)?,
         Mode::Compile => compile_and_run(exercise)?,
         Mode::Clippy => compile_and_run(exercise)?,
     }
     Ok(())
 }
 
-// Resets the exercise by stashing the changes.
-pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
-        .args(["stash", "--"])
-        .arg(&exercise.path)
-        .spawn();
-
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+// Resets the exercise to the copy that was kept of it as it was given,
+// backing up the current attempt under `backup_dir` if there is one.
+// Returns whether the exercise had changed
+pub fn reset(
+    exercise: &Exercise,
+    pristine: &Pristine,
+    backup_dir: Option<&Path>,
+) -> Result<bool, ()> {
+    match pristine.restore(exercise, backup_dir) {
+        Ok(Reset::Restored(backup)) => {
+            if let Some(backup) = backup {
+                println!("Your attempt was backed up to {}", backup.display());
+            }
+            success!("Reset {}", exercise);
+            Ok(true)
+        }
+        Ok(Reset::Unchanged) => Ok(false),
+        Err(e) => {
+            warn!("Failed to reset {}", exercise);
+            println!("{e}");
+            Err(())
+        }
     }
 }
 
@@ -41,13 +63,13 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
     let compilation_result = exercise.compile();
     let compilation = match compilation_result {
         Ok(compilation) => compilation,
-        Err(output) => {
+        Err(error) => {
             progress_bar.finish_and_clear();
             warn!(
                 "Compilation of {} failed!, Compiler error message:\n",
                 exercise
             );
-            println!("{}", output.stderr);
+            println!("{}", error.focused());
             return Err(());
         }
     };
@@ -62,12 +84,18 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
             success!("Successfully ran {}", exercise);
             Ok(())
         }
-        Err(output) => {
+        Err(RunError::Failed(output)) => {
             println!("{}", output.stdout);
             println!("{}", output.stderr);
 
             warn!("Ran {} with errors", exercise);
             Err(())
         }
+        Err(RunError::TimedOut(limit, output)) => {
+            println!("{}", output.stdout);
+
+            warn_timed_out(exercise, limit);
+            Err(())
+        }
     }
 }
@@ -0,0 +1,63 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process;
+
+// A JSON file where rustlings keeps its state between runs, like the progress or the packs.
+// A file that exists but can't be read or parsed is left alone: nothing is saved over it,
+// so that fixing the file brings back what it held
+#[derive(Default, Debug)]
+pub struct StateFile {
+    path: PathBuf,
+    unreadable: bool,
+}
+
+impl StateFile {
+    // Load the state saved in the given file.
+    // A missing file gives the default state, and so does a file that can't be read or parsed,
+    // which is then marked as unreadable
+    pub fn load<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> (StateFile, T) {
+        let path = path.as_ref().to_path_buf();
+        let (state, unreadable) = match fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(state) => (state, false),
+                Err(_) => (T::default(), true),
+            },
+            Err(e) if e.kind() == ErrorKind::NotFound => (T::default(), false),
+            Err(_) => (T::default(), true),
+        };
+        (StateFile { path, unreadable }, state)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Whether the file exists but could not be read, so the state is not saved
+    pub fn is_unreadable(&self) -> bool {
+        self.unreadable
+    }
+
+    // Write the state to a temporary file next to the file, then move it into place,
+    // so that an interrupted save never leaves a truncated file behind
+    pub fn save(&self, state: &impl Serialize) -> io::Result<()> {
+        if self.unreadable {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{} could not be read", self.path.display()),
+            ));
+        }
+        let contents = serde_json::to_string_pretty(state)?;
+        let mut temp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(format!(".{}.tmp", process::id()));
+        let temp_path = self.path.with_file_name(temp_name);
+        fs::write(&temp_path, contents + "\n")?;
+        if let Err(e) = fs::rename(&temp_path, &self.path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
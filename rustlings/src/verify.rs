@@ -1,148 +1,298 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::exercise::{CompileError, Exercise, ExerciseOutput, Mode, RunError, State};
+use crate::progress::{Progress, PROGRESS_FILE};
+use crate::report::{print_json, ExerciseReport, Format};
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::BTreeMap;
 use std::env;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
 // If the Exercise being verified is a test, the verbose boolean
 // determines whether or not the test harness outputs are displayed.
+// Every exercise that passes is recorded in the progress file,
+// together with the hash of its source.
+// With more than one job, the exercises are compiled and run concurrently,
+// but they are still reported one after another in the given order.
+// In the JSON format, every exercise is checked, even after one failed,
+// and a report of all of them is printed at the end.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
     verbose: bool,
+    jobs: usize,
+    format: Format,
+    verified: &mut Progress,
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
+    let bar = match format {
+        Format::Text => ProgressBar::new(total as u64),
+        Format::Json => ProgressBar::hidden(),
+    };
     let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(ProgressStyle::default_bar()
-        .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-        .progress_chars("#>-")
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
+            .progress_chars("#>-"),
     );
     bar.set_position(num_done as u64);
     bar.set_message(format!("({:.1} %)", percentage));
 
-    for exercise in exercises {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose),
-            Mode::Compile => compile_and_run_interactively(exercise),
-            Mode::Clippy => compile_only(exercise),
+    let exercises: Vec<&'a Exercise> = exercises.into_iter().collect();
+    let require_marker = verified.require_marker();
+    let mut failed = None;
+    let mut reports = Vec::new();
+    let mut report_and_record = |exercise: &'a Exercise, outcome: Outcome| {
+        let result = match format {
+            Format::Text => report(exercise, outcome, verbose, require_marker),
+            Format::Json => {
+                let passed = matches!(outcome, Outcome::Passed(_));
+                reports.push((exercise, outcome));
+                if passed {
+                    Ok(true)
+                } else {
+                    Err(())
+                }
+            }
         };
-        if !compile_result.unwrap_or(false) {
-            return Err(exercise);
+        let saved = match result {
+            Ok(_) => verified.record(exercise),
+            Err(_) => verified.forget(exercise),
+        };
+        // An unreadable progress file was already reported when it was loaded
+        if saved.is_err() && !verified.is_unreadable() && format == Format::Text {
+            warn!("Could not save your progress to {}", PROGRESS_FILE);
+        }
+        if !result.unwrap_or(false) {
+            failed.get_or_insert(exercise);
+            return format == Format::Json;
         }
         percentage += 100.0 / total as f32;
         bar.inc(1);
         bar.set_message(format!("({:.1} %)", percentage));
+        true
+    };
+
+    if jobs > 1 {
+        check_in_parallel(&exercises, jobs, report_and_record);
+    } else {
+        for exercise in exercises {
+            let progress_bar = match format {
+                Format::Text => ProgressBar::new_spinner(),
+                Format::Json => ProgressBar::hidden(),
+            };
+            let outcome = check(exercise, &progress_bar);
+            if !report_and_record(exercise, outcome) {
+                break;
+            }
+        }
     }
-    Ok(())
-}
 
-enum RunMode {
-    Interactive,
-    NonInteractive,
+    if format == Format::Json {
+        let reports: Vec<ExerciseReport> = reports
+            .into_iter()
+            .map(|(exercise, outcome)| {
+                let done = verified.is_done(exercise);
+                ExerciseReport::new(exercise, outcome, done)
+            })
+            .collect();
+        print_json(&reports);
+    }
+
+    match failed {
+        Some(exercise) => Err(exercise),
+        None => Ok(()),
+    }
 }
 
 // Compile and run the resulting test harness of the given Exercise
 pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose)?;
+    let outcome = check(exercise, &ProgressBar::new_spinner());
+    // There is nothing to prompt for when running a single exercise
+    report(exercise, outcome, verbose, false)?;
     Ok(())
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
-
-    let _ = compile(exercise, &progress_bar)?;
-    progress_bar.finish_and_clear();
-
-    Ok(prompt_for_completion(exercise, None))
+// What came out of compiling and running an exercise,
+// kept until it is its turn to be shown to the user
+pub enum Outcome {
+    // The exercise passed, with the output of its binary if it was run
+    Passed(Option<ExerciseOutput>),
+    // The exercise did not compile, or Clippy was not happy with it
+    CompileFailed(CompileError),
+    // The exercise compiled, but its binary failed or timed out
+    RunFailed(RunError),
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
+// Compile the given Exercise and, unless it only needs to satisfy Clippy,
+// run the resulting binary, without showing anything but the progress bar
+pub fn check(exercise: &Exercise, progress_bar: &ProgressBar) -> Outcome {
+    match exercise.mode {
+        Mode::Test | Mode::Cargo => progress_bar.set_message(format!("Testing {exercise}...")),
+        _ => progress_bar.set_message(format!("Compiling {exercise}...")),
+    }
     progress_bar.enable_steady_tick(100);
 
-    let compilation = compile(exercise, &progress_bar)?;
-
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
-
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-            return Err(());
+    let outcome = match exercise.compile() {
+        Err(error) => Outcome::CompileFailed(error),
+        Ok(_) if matches!(exercise.mode, Mode::Clippy) => Outcome::Passed(None),
+        Ok(compilation) => {
+            if let Mode::Compile = exercise.mode {
+                progress_bar.set_message(format!("Running {exercise}..."));
+            }
+            match compilation.run() {
+                Ok(output) => Outcome::Passed(Some(output)),
+                Err(error) => Outcome::RunFailed(error),
+            }
         }
     };
-
-    Ok(prompt_for_completion(exercise, Some(output.stdout)))
+    progress_bar.finish_and_clear();
+    outcome
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+// Check the exercises in the given number of threads, and hand the outcome
+// of each one to `report` in the order of the exercises.
+// Once `report` returns false, no more exercises are checked or reported.
+fn check_in_parallel<'a>(
+    exercises: &[&'a Exercise],
+    jobs: usize,
+    mut report: impl FnMut(&'a Exercise, Outcome) -> bool,
+) {
+    // The threads get their own copy of the exercises, which they share
+    let owned: Arc<Vec<Exercise>> = Arc::new(exercises.iter().map(|&e| e.clone()).collect());
+    let next = Arc::new(AtomicUsize::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
 
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+    let workers: Vec<_> = (0..jobs.min(exercises.len()))
+        .map(|_| {
+            let sender = sender.clone();
+            let (owned, next, stop) = (Arc::clone(&owned), Arc::clone(&next), Arc::clone(&stop));
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let exercise = match owned.get(index) {
+                        Some(exercise) => exercise,
+                        None => break,
+                    };
+                    let outcome = check(exercise, &ProgressBar::hidden());
+                    if sender.send((index, outcome)).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(sender);
 
-    match result {
-        Ok(output) => {
-            if verbose {
-                println!("{}", output.stdout);
-            }
-            if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(exercise, None))
-            } else {
-                Ok(true)
+    // Outcomes arrive in the order the exercises finish,
+    // so keep them until all the ones before them were reported
+    let mut finished = BTreeMap::new();
+    let mut reported = 0;
+    for (index, outcome) in receiver {
+        if stop.load(Ordering::Relaxed) {
+            continue;
+        }
+        finished.insert(index, outcome);
+        while let Some(outcome) = finished.remove(&reported) {
+            if !report(exercises[reported], outcome) {
+                stop.store(true, Ordering::Relaxed);
+                break;
             }
+            reported += 1;
         }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
-            Err(())
+    }
+
+    // A panic in a thread is passed on, like it would be without threads
+    for worker in workers {
+        if let Err(e) = worker.join() {
+            std::panic::resume_unwind(e);
         }
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a, 'b>(
-    exercise: &'a Exercise,
-    progress_bar: &'b ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
-
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
+// Show the outcome of an exercise to the user.
+// Returns whether it can be considered done, or an error if it failed
+fn report(
+    exercise: &Exercise,
+    outcome: Outcome,
+    verbose: bool,
+    require_marker: bool,
+) -> Result<bool, ()> {
+    match outcome {
+        Outcome::Passed(output) => match exercise.mode {
+            Mode::Test | Mode::Cargo => {
+                if let (true, Some(output)) = (verbose, output) {
+                    println!("{}", output.stdout);
+                }
+                Ok(prompt_for_completion(exercise, None, require_marker))
+            }
+            Mode::Compile => Ok(prompt_for_completion(
+                exercise,
+                output.map(|output| output.stdout),
+                require_marker,
+            )),
+            Mode::Clippy => Ok(prompt_for_completion(exercise, None, require_marker)),
+        },
+        Outcome::CompileFailed(error) => {
             warn!(
                 "Compiling of {} failed! Please try again. Here's the output:",
                 exercise
             );
-            println!("{}", output.stderr);
+            println!("{}", error.focused());
+            Err(())
+        }
+        Outcome::RunFailed(RunError::TimedOut(limit, output)) => {
+            warn_timed_out(exercise, limit);
+            println!("{}", output.stdout);
+            Err(())
+        }
+        Outcome::RunFailed(RunError::Failed(output)) => {
+            if let Mode::Test | Mode::Cargo = exercise.mode {
+                warn!(
+                    "Testing of {} failed! Please try again. Here's the output:",
+                    exercise
+                );
+                println!("{}", output.stdout);
+                if let Mode::Cargo = exercise.mode {
+                    println!("{}", output.stderr);
+                }
+            } else {
+                warn!("Ran {} with errors", exercise);
+                println!("{}", output.stdout);
+                println!("{}", output.stderr);
+            }
             Err(())
         }
     }
 }
 
-fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) -> bool {
+// Tell the user that the exercise binary was stopped for running too long
+pub fn warn_timed_out(exercise: &Exercise, limit: Duration) {
+    warn!("{} timed out!", exercise);
+    println!(
+        "It was stopped after running for {} seconds.",
+        limit.as_secs()
+    );
+    println!("Look for a loop that never ends, or for threads that wait on each other forever.");
+}
+
+// Tell the user that the exercise passed and, when the marker is required
+// but still in place, show where to remove it to move on
+fn prompt_for_completion(
+    exercise: &Exercise,
+    prompt_output: Option<String>,
+    require_marker: bool,
+) -> bool {
+    if !require_marker {
+        return true;
+    }
     let context = match exercise.state() {
         State::Done => return true,
         State::Pending(context) => context,
@@ -150,7 +300,7 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
 
     match exercise.mode {
         Mode::Compile => success!("Successfully ran {}!", exercise),
-        Mode::Test => success!("Successfully tested {}!", exercise),
+        Mode::Test | Mode::Cargo => success!("Successfully tested {}!", exercise),
         Mode::Clippy => success!("Successfully compiled {}!", exercise),
     }
 
@@ -166,6 +316,7 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
         Mode::Compile => "The code is compiling!",
         Mode::Test => "The code is compiling, and the tests pass!",
         Mode::Clippy => clippy_success_msg,
+        Mode::Cargo => "The crate is building, and the tests pass!",
     };
 
     println!();
@@ -0,0 +1,9 @@
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    println!("Waiting forever...");
+    loop {
+        thread::sleep(Duration::from_millis(100));
+    }
+}
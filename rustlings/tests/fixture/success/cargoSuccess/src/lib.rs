@@ -0,0 +1,3 @@
+mod math;
+
+pub use math::add;
@@ -0,0 +1,3 @@
+fn main() {
+    println!("{}", cargo_success::add(1, 2));
+}
@@ -0,0 +1,7 @@
+use cargo_success::add;
+
+#[test]
+fn adds_negative_numbers() {
+    println!("THE CRATE TESTS PASS");
+    assert_eq!(add(-1, -2), -3);
+}
@@ -3,48 +3,107 @@ use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
 use std::fs::File;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+// Run rustlings with a home of its own in the target directory,
+// so that the exercises are not compiled in the home of whoever runs the tests
+fn rustlings() -> Command {
+    let mut cmd = Command::cargo_bin("rustlings").unwrap();
+    cmd.env(
+        "RUSTLINGS_HOME",
+        Path::new(env!("CARGO_TARGET_TMPDIR")).join("home"),
+    );
+    cmd
+}
+
+// Copy a fixture to a temporary directory, for the tests that write to it,
+// like `verify` saving the progress, so that the tracked fixture stays untouched
+fn temp_fixture(fixture: &str, test: &str) -> PathBuf {
+    fn copy(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let name = entry.file_name();
+            if name == "target" || name == ".rustlings-pristine" {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                copy(&entry.path(), &to.join(name));
+            } else {
+                std::fs::copy(entry.path(), to.join(name)).unwrap();
+            }
+        }
+    }
+
+    let dir = std::env::temp_dir().join(format!("rustlings_{test}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    copy(&Path::new("tests/fixture").join(fixture), &dir);
+    dir
+}
+
 #[test]
 fn runs_without_arguments() {
-    let mut cmd = Command::cargo_bin("rustlings").unwrap();
-    cmd.assert().success();
+    rustlings().assert().success();
 }
 
 #[test]
 fn fails_when_in_wrong_dir() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    rustlings()
         .current_dir("tests/")
+        .env("RUSTLINGS_HOME", "fixture/missing")
         .assert()
         .code(1);
 }
 
 #[test]
 fn verify_all_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    let dir = temp_fixture("success", "verify");
+    rustlings()
         .arg("verify")
-        .current_dir("tests/fixture/success")
+        .current_dir(&dir)
         .assert()
         .success();
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn verify_fails_if_some_fails() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    rustlings()
         .arg("verify")
         .current_dir("tests/fixture/failure")
         .assert()
         .code(1);
 }
 
+#[test]
+fn verify_all_success_in_parallel() {
+    let dir = temp_fixture("success", "verify_parallel");
+    rustlings()
+        .args(["verify", "--jobs", "4"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_in_parallel_stops_at_first_failure() {
+    rustlings()
+        .args(["verify", "--jobs", "4"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("compFailure.rs")
+                .and(predicates::str::contains("testFailure.rs").not()),
+        );
+}
+
 #[test]
 fn run_single_compile_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "compSuccess"])
+    rustlings()
+        .args(["run", "compSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -52,19 +111,83 @@ fn run_single_compile_success() {
 
 #[test]
 fn run_single_compile_failure() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "compFailure"])
+    rustlings()
+        .args(["run", "compFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
 }
 
+#[test]
+fn failed_run_removes_its_build_dir() {
+    let dir = temp_fixture("failure", "exit");
+    let home = std::env::temp_dir().join(format!("rustlings_exit_home_{}", std::process::id()));
+    rustlings()
+        .args(["run", "testFailure"])
+        .current_dir(&dir)
+        .env("RUSTLINGS_HOME", &home)
+        .assert()
+        .code(1);
+    // The exercise is built in the home, and nothing is written into the pack
+    assert!(home.join("target").is_dir());
+    assert!(!dir.join("target").exists());
+    let runs = glob(home.join("target/**/run-*").to_str().unwrap()).unwrap();
+    assert_eq!(runs.count(), 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+    std::fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn run_single_compile_failure_shows_first_error() {
+    let dir = std::env::temp_dir().join(format!("rustlings_errors_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        r#"
+[[exercises]]
+name = "errors"
+path = "errors.rs"
+mode = "compile"
+hint = ""
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("errors.rs"),
+        "fn main() {\n    let x: i32 = \"one\";\n    missing();\n}\n",
+    )
+    .unwrap();
+
+    rustlings()
+        .args(["run", "errors"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("error[E0308]")
+                .and(predicates::str::contains("errors.rs:2:18"))
+                .and(predicates::str::contains("rustc --explain E0308"))
+                .and(predicates::str::contains("1 more error is hidden"))
+                .and(predicates::str::contains("E0425").not()),
+        );
+    // The JSON report keeps every error
+    rustlings()
+        .args(["verify", "--format", "json"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("error[E0308]")
+                .and(predicates::str::contains("error[E0425]"))
+                .and(predicates::str::contains("more error is hidden").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn run_single_test_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "testSuccess"])
+    rustlings()
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -72,9 +195,8 @@ fn run_single_test_success() {
 
 #[test]
 fn run_single_test_failure() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "testFailure"])
+    rustlings()
+        .args(["run", "testFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -82,18 +204,46 @@ fn run_single_test_failure() {
 
 #[test]
 fn run_single_test_not_passed() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "testNotPassed.rs"])
+    rustlings()
+        .args(["run", "testNotPassed.rs"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
 }
 
+#[test]
+fn run_single_compile_timeout() {
+    rustlings()
+        .args(["run", "compTimeout"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("timed out"));
+}
+
+#[test]
+fn run_single_cargo_success() {
+    rustlings()
+        .args(["--nocapture", "run", "cargoSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("THE CRATE TESTS PASS"));
+}
+
+#[test]
+fn run_single_cargo_failure() {
+    rustlings()
+        .args(["run", "cargoFailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("test result: FAILED"));
+}
+
 #[test]
 fn run_single_test_no_filename() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    rustlings()
         .arg("run")
         .current_dir("tests/fixture/")
         .assert()
@@ -102,9 +252,8 @@ fn run_single_test_no_filename() {
 
 #[test]
 fn run_single_test_no_exercise() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "compNoExercise.rs"])
+    rustlings()
+        .args(["run", "compNoExercise.rs"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(1);
@@ -112,36 +261,162 @@ fn run_single_test_no_exercise() {
 
 #[test]
 fn reset_single_exercise() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["reset", "intro1"])
-        .assert()
-        .code(0);
+    rustlings().args(["reset", "intro1"]).assert().code(0);
 }
 
 #[test]
 fn reset_no_exercise() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    rustlings()
         .arg("reset")
         .assert()
         .code(1)
-        .stderr(predicates::str::contains(
-            "positional arguments not provided",
-        ));
+        .stdout(predicates::str::contains("--all"));
+}
+
+#[test]
+fn reset_restores_the_given_exercise() {
+    let dir = std::env::temp_dir().join(format!("rustlings_reset_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        r#"
+[[exercises]]
+name = "first"
+path = "first.rs"
+mode = "compile"
+hint = ""
+
+[[exercises]]
+name = "second"
+path = "second.rs"
+mode = "compile"
+hint = ""
+"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("first.rs"), "// given").unwrap();
+    std::fs::write(dir.join("second.rs"), "// given").unwrap();
+
+    let home = dir.join("home");
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args)
+            .current_dir(&dir)
+            .env("RUSTLINGS_HOME", &home);
+        cmd.assert()
+    };
+    // Without knowing how the exercises were given, they can't be reset
+    rustlings(&["reset", "first"])
+        .code(1)
+        .stdout(predicates::str::contains("does not tell how"));
+    // Rustlings keeps the exercises as they are when their pack is added
+    rustlings(&["pack", "add", "."]).code(0);
+    std::fs::write(dir.join("first.rs"), "// attempt").unwrap();
+    std::fs::write(dir.join("second.rs"), "// attempt").unwrap();
+
+    rustlings(&["reset", "--backup", "first"])
+        .code(0)
+        .stdout(predicates::str::contains("backed up"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("first.rs")).unwrap(),
+        "// given"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("second.rs")).unwrap(),
+        "// attempt"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join(".rustlings-backup/first.rs")).unwrap(),
+        "// attempt"
+    );
+
+    rustlings(&["reset", "--all"]).code(0);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("second.rs")).unwrap(),
+        "// given"
+    );
+    rustlings(&["reset", "--all"])
+        .code(0)
+        .stdout(predicates::str::contains("Nothing to reset"));
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn get_hint_for_single_test() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["hint", "testFailure"])
+    rustlings()
+        .args(["hint", "testFailure"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(0)
         .stdout("Hello!\n");
 }
 
+#[test]
+fn get_hint_levels_one_at_a_time() {
+    let dir = std::env::temp_dir().join(format!("rustlings_hints_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        r#"
+[[exercises]]
+name = "levels"
+path = "levels.rs"
+mode = "compile"
+hint = ["First", "Second"]
+"#,
+    )
+    .unwrap();
+    let _ = std::fs::remove_file(dir.join(".rustlings-hints.json"));
+
+    let hint = || {
+        rustlings()
+            .args(["hint", "levels"])
+            .current_dir(&dir)
+            .assert()
+            .code(0)
+    };
+    hint().stdout(
+        predicates::str::contains("First")
+            .and(predicates::str::contains("Second").not())
+            .and(predicates::str::contains("again for the next hint")),
+    );
+    hint().stdout(predicates::str::contains("First").and(predicates::str::contains("Second")));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn use_a_pack_from_another_dir() {
+    let home = std::env::temp_dir().join(format!("rustlings_home_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&home);
+    // Adding a pack writes to it, to keep its exercises as they were given
+    let pack = temp_fixture("success", "pack");
+
+    let rustlings = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rustlings").unwrap();
+        cmd.args(args)
+            .current_dir("tests/")
+            .env("RUSTLINGS_HOME", &home);
+        cmd.assert()
+    };
+    rustlings(&["pack", "add", pack.to_str().unwrap(), "--name", "course"])
+        .code(0)
+        .stdout(predicates::str::contains("Added the 'course' pack"));
+    rustlings(&["pack", "add", "fixture"]).code(1);
+    rustlings(&["pack", "use", "missing"]).code(1);
+    rustlings(&["list", "--names"]).code(1);
+
+    rustlings(&["pack", "use", "course"]).code(0);
+    rustlings(&["pack", "list"])
+        .code(0)
+        .stdout(predicates::str::contains("* course"));
+    rustlings(&["list", "--names"])
+        .code(0)
+        .stdout(predicates::str::contains("testSuccess"));
+    std::fs::remove_dir_all(&home).unwrap();
+    std::fs::remove_dir_all(&pack).unwrap();
+}
+
 #[test]
 fn all_exercises_require_confirmation() {
     for exercise in glob("exercises/**/*.rs").unwrap() {
@@ -169,9 +444,8 @@ fn all_exercises_require_confirmation() {
 
 #[test]
 fn run_compile_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "pending_exercise"])
+    rustlings()
+        .args(["run", "pending_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
@@ -180,9 +454,8 @@ fn run_compile_exercise_does_not_prompt() {
 
 #[test]
 fn run_test_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "pending_test_exercise"])
+    rustlings()
+        .args(["run", "pending_test_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
@@ -191,9 +464,8 @@ fn run_test_exercise_does_not_prompt() {
 
 #[test]
 fn run_single_test_success_with_output() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["--nocapture", "run", "testSuccess"])
+    rustlings()
+        .args(["--nocapture", "run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
@@ -202,9 +474,8 @@ fn run_single_test_success_with_output() {
 
 #[test]
 fn run_single_test_success_without_output() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["run", "testSuccess"])
+    rustlings()
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
@@ -213,9 +484,8 @@ fn run_single_test_success_without_output() {
 
 #[test]
 fn run_rustlings_list() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["list"])
+    rustlings()
+        .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success();
@@ -223,9 +493,8 @@ fn run_rustlings_list() {
 
 #[test]
 fn run_rustlings_list_no_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["list"])
+    rustlings()
+        .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success()
@@ -234,9 +503,8 @@ fn run_rustlings_list_no_pending() {
 
 #[test]
 fn run_rustlings_list_both_done_and_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["list"])
+    rustlings()
+        .args(["list"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -245,9 +513,8 @@ fn run_rustlings_list_both_done_and_pending() {
 
 #[test]
 fn run_rustlings_list_without_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["list", "--solved"])
+    rustlings()
+        .args(["list", "--solved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -256,11 +523,67 @@ fn run_rustlings_list_without_pending() {
 
 #[test]
 fn run_rustlings_list_without_done() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
-        .args(&["list", "--unsolved"])
+    rustlings()
+        .args(["list", "--unsolved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_rustlings_list_json() {
+    let output = rustlings()
+        .args(["list", "--format", "json"])
+        .current_dir("tests/fixture/state")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let finished = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["name"] == "finished_exercise")
+        .unwrap();
+    assert_eq!(finished["path"], "finished_exercise.rs");
+    assert_eq!(finished["mode"], "compile");
+    assert_eq!(finished["state"], "done");
+}
+
+#[test]
+fn verify_json_reports_every_exercise() {
+    let output = rustlings()
+        .args(["verify", "--format", "json"])
+        .current_dir("tests/fixture/failure")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let reports: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let reports = reports.as_array().unwrap();
+    assert_eq!(reports.len(), 4);
+    assert_eq!(reports[0]["name"], "compFailure");
+    assert_eq!(reports[0]["compiled"], false);
+    assert_eq!(reports[2]["timed_out"], true);
+    assert_eq!(reports[3]["tests"]["failed"], 1);
+}
+
+#[test]
+fn run_single_test_json() {
+    let output = rustlings()
+        .args(["run", "testSuccess", "--format", "json"])
+        .current_dir("tests/fixture/success")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["passed"], true);
+    assert_eq!(report["tests"]["passed"], 1);
+    assert!(report["stdout"]
+        .as_str()
+        .unwrap()
+        .contains("THIS TEST TOO SHALL PASS"));
+}